@@ -1,3 +1,4 @@
+use baidupan_sdk_rust::models::file::{OnConflict, OperationMode};
 use baidupan_sdk_rust::{BaiduPanClient, Config};
 use std::io::{self, Write};
 use tokio::time::{sleep, Duration};
@@ -186,7 +187,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("─────────────────────────────────────────");
 
     let test_folder = "/SDK_TEST_DEMO";
-    match client.create_dir(test_folder).await {
+    match client.create_dir(test_folder, OnConflict::Fail).await {
         Ok(_) => {
             println!("✅ 文件夹创建成功: {}", test_folder);
         }
@@ -221,12 +222,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n✏️  步骤 10: 文件操作测试");
     println!("─────────────────────────────────────────");
 
-    match client.rename_file(test_folder, "SDK_TEST_RENAMED").await {
+    match client.rename_file(test_folder, "SDK_TEST_RENAMED", OperationMode::Sync).await {
         Ok(_) => {
             println!("✅ 文件夹重命名成功: {} -> /SDK_TEST_RENAMED", test_folder);
 
             // 删除测试文件夹
-            match client.delete_files(&["/SDK_TEST_RENAMED"]).await {
+            match client.delete_files(&["/SDK_TEST_RENAMED"], OperationMode::Sync).await {
                 Ok(_) => {
                     println!("✅ 测试文件夹已删除");
                 }
@@ -1,3 +1,4 @@
+use baidupan_sdk_rust::models::upload::{UploadEvent, UploadOptions};
 use baidupan_sdk_rust::{BaiduPanClient, Config};
 use std::path::Path;
 
@@ -39,7 +40,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   本地路径: {:?}", local_file);
     println!("   远程路径: {}\n", remote_path);
 
-    match client.upload_file(local_file, remote_path, None).await {
+    // 打印上传进度
+    let options = UploadOptions::new().with_progress(|event| match event {
+        UploadEvent::Precreated { rapid_upload: true, .. } => println!("   ⚡ 秒传命中"),
+        UploadEvent::SliceProgress {
+            total_sent,
+            total_bytes,
+            ..
+        } => println!("   进度: {}/{} 字节", total_sent, total_bytes),
+        UploadEvent::SliceRetry {
+            partseq, attempt, ..
+        } => println!("   分片 {} 第 {} 次重试", partseq, attempt),
+        _ => {}
+    });
+
+    match client
+        .upload_file_with_options(local_file, remote_path, &options)
+        .await
+    {
         Ok(response) => {
             println!("✅ 上传成功!");
             println!("   文件 ID: {}", response.fs_id);
@@ -0,0 +1,482 @@
+use crate::api::filemanager::{describe_entry, entries_record, info_fs_ids, move_file_entry};
+use crate::client::ApiResponse;
+use crate::models::file::{
+    BatchItemResult, BatchOptions, BatchOutcome, FileErrorKind, FileMoveItem, FileOperationInfo,
+    FileOperationResponse, OnDuplicate, OperationMode, PendingItem,
+};
+use crate::models::path::ToRemotePath;
+use crate::{BaiduPanClient, Error, Result};
+use futures_util::{StreamExt, stream};
+use serde_json::{Value, json};
+
+/// 批量操作部分文件失败时的整体错误码
+const ERRNO_PARTIAL_FAILURE: i32 = 12;
+
+impl BaiduPanClient {
+    /// 批量删除文件，返回每个文件的结果
+    ///
+    /// 与 [`BaiduPanClient::delete_files`] 不同，部分文件失败时不会返回错误，
+    /// 而是在结果中区分成功和失败的文件；服务器异步执行时自动等待任务结束。
+    ///
+    /// 文件数量超过 `options.chunk_size` 时拆分为多次请求，以 `options.concurrency`
+    /// 的并发执行后合并结果。某次请求整体失败时，该请求中的文件全部记为失败，不影响其他请求。
+    ///
+    /// # 参数
+    ///
+    /// * `paths` - 要删除的文件路径列表
+    /// * `options` - 批量操作选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::file::BatchOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let options = BatchOptions::new();
+    /// let outcome = client.batch_delete(&["/a.txt", "/b.txt"], &options).await?;
+    /// for failure in &outcome.failed {
+    ///     println!("{} 删除失败: {:?}", failure.source, failure.error);
+    /// }
+    ///
+    /// // 只重试失败的文件
+    /// client.batch_delete(&outcome.failed_items(), &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn batch_delete(
+        &self,
+        paths: &[impl ToRemotePath],
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<String>> {
+        let items = paths
+            .iter()
+            .map(|p| p.to_remote_path().map(String::from))
+            .collect::<Result<Vec<_>>>()?;
+        self.run_chunked(
+            "delete",
+            &items,
+            options,
+            None,
+            |p| Ok(json!({"path": p})),
+            |p| (p.clone(), None),
+        )
+        .await
+    }
+
+    /// 批量移动文件，返回每个文件的结果
+    ///
+    /// 大批量时的拆分方式与 [`BaiduPanClient::batch_delete`] 相同。
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 要移动的文件项
+    /// * `on_duplicate` - 目标路径已存在时的处理策略
+    /// * `options` - 批量操作选项
+    pub async fn batch_move(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<FileMoveItem>> {
+        self.batch_move_or_copy("move", items, on_duplicate, options)
+            .await
+    }
+
+    /// 批量复制文件，返回每个文件的结果
+    ///
+    /// 大批量时的拆分方式与 [`BaiduPanClient::batch_delete`] 相同。
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 要复制的文件项
+    /// * `on_duplicate` - 目标路径已存在时的处理策略
+    /// * `options` - 批量操作选项
+    pub async fn batch_copy(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<FileMoveItem>> {
+        self.batch_move_or_copy("copy", items, on_duplicate, options)
+            .await
+    }
+
+    /// 批量移动或复制
+    async fn batch_move_or_copy(
+        &self,
+        opera: &str,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<FileMoveItem>> {
+        let describe = |item: &FileMoveItem| {
+            let dest = item.dest.trim_end_matches('/');
+            (
+                item.path.clone(),
+                Some(format!("{}/{}", dest, item.target_name())),
+            )
+        };
+        self.run_chunked(
+            opera,
+            items,
+            options,
+            Some(on_duplicate),
+            move_file_entry,
+            describe,
+        )
+        .await
+    }
+
+    /// 把文件项拆分为多次请求并发执行，按原顺序合并结果
+    async fn run_chunked<T: Clone>(
+        &self,
+        opera: &str,
+        items: &[T],
+        options: &BatchOptions,
+        on_duplicate: Option<OnDuplicate>,
+        to_entry: impl Fn(&T) -> Result<Value>,
+        describe: impl Fn(&T) -> (String, Option<String>),
+    ) -> Result<BatchOutcome<T>> {
+        // 先校验全部文件项，避免执行到一半才发现参数错误
+        let file_list = items.iter().map(to_entry).collect::<Result<Vec<_>>>()?;
+        let targets: Vec<String> = file_list.iter().map(describe_entry).collect();
+        if self.check_mutation(opera, &targets)? {
+            return Ok(collect_outcome(items.to_vec(), describe, 0, Vec::new()));
+        }
+
+        let chunk_size = options.chunk_size.max(1);
+        let describe = &describe;
+
+        let outcomes: Vec<BatchOutcome<T>> =
+            stream::iter(items.chunks(chunk_size).zip(file_list.chunks(chunk_size)))
+                .map(|(items, file_list)| async move {
+                    match self
+                        .run_batch(opera, file_list, options, on_duplicate)
+                        .await
+                    {
+                        Ok((errno, info)) => collect_outcome(items.to_vec(), describe, errno, info),
+                        Err(error) => {
+                            if self.config.debug {
+                                eprintln!("批量操作请求失败({} 个文件): {}", items.len(), error);
+                            }
+                            failed_outcome(items.to_vec(), describe, &error)
+                        }
+                    }
+                })
+                .buffered(options.concurrency.max(1))
+                .collect()
+                .await;
+
+        let mut merged = BatchOutcome::default();
+        for outcome in outcomes {
+            merged.succeeded.extend(outcome.succeeded);
+            merged.failed.extend(outcome.failed);
+            merged.pending.extend(outcome.pending);
+        }
+        Ok(merged)
+    }
+
+    /// 执行批量操作，返回整体错误码和每个文件的信息，并写入审计记录
+    async fn run_batch(
+        &self,
+        opera: &str,
+        file_list: &[Value],
+        options: &BatchOptions,
+        on_duplicate: Option<OnDuplicate>,
+    ) -> Result<(i32, Vec<FileOperationInfo>)> {
+        let result = self
+            .run_batch_request(opera, file_list, options, on_duplicate)
+            .await;
+
+        let record = entries_record(opera, file_list);
+        let record = match &result {
+            Ok((errno, info, request_id)) => {
+                let record = record.succeeded(info_fs_ids(info), request_id.clone());
+                if *errno != 0 {
+                    record.partially_failed(*errno)
+                } else {
+                    record
+                }
+            }
+            Err(error) => record.failed(error),
+        };
+        self.audit(record).await;
+
+        result.map(|(errno, info, _)| (errno, info))
+    }
+
+    /// 发送批量操作请求，返回整体错误码、每个文件的信息和请求 ID
+    ///
+    /// 部分文件失败(errno=12)时不返回错误；服务器返回 `taskid` 时等待任务结束并使用任务结果。
+    async fn run_batch_request(
+        &self,
+        opera: &str,
+        file_list: &[Value],
+        options: &BatchOptions,
+        on_duplicate: Option<OnDuplicate>,
+    ) -> Result<(i32, Vec<FileOperationInfo>, Option<String>)> {
+        let response = self
+            .file_manager_request(opera, file_list, options.mode, on_duplicate)
+            .await?;
+
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(Error::ApiError {
+                errno: status.as_u16() as i32,
+                message: text,
+            });
+        }
+
+        let response: ApiResponse<FileOperationResponse> = serde_json::from_str(&text)?;
+        if response.errno != 0 && response.errno != ERRNO_PARTIAL_FAILURE {
+            return Err(Error::ApiError {
+                errno: response.errno,
+                message: response
+                    .errmsg
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            });
+        }
+        let errno = response.errno;
+        let data = response.data;
+        let request_id = data.as_ref().and_then(|d| d.request_id.clone());
+
+        if options.mode != OperationMode::Sync
+            && let Some(taskid) = data.as_ref().and_then(|d| d.taskid)
+        {
+            let result = self.file_task(taskid).wait(&options.poll).await?;
+            let errno = if result.is_success() {
+                0
+            } else {
+                result
+                    .task_errno
+                    .filter(|&e| e != 0)
+                    .unwrap_or(ERRNO_PARTIAL_FAILURE)
+            };
+            return Ok((errno, result.list, request_id));
+        }
+
+        Ok((
+            errno,
+            data.and_then(|d| d.info).unwrap_or_default(),
+            request_id,
+        ))
+    }
+}
+
+/// 把服务器返回的文件信息与请求的文件项对应起来
+///
+/// 信息数量与请求一致时按顺序对应，否则按源路径匹配。没有对应信息的文件：部分失败(errno=12)时
+/// 服务器只返回了失败的文件，记为成功；整个请求失败时使用整体错误码。
+fn collect_outcome<T>(
+    items: Vec<T>,
+    describe: impl Fn(&T) -> (String, Option<String>),
+    errno: i32,
+    info: Vec<FileOperationInfo>,
+) -> BatchOutcome<T> {
+    let by_index = info.len() == items.len();
+    let unmatched_errno = match errno {
+        ERRNO_PARTIAL_FAILURE => 0,
+        errno => errno,
+    };
+    let mut outcome = BatchOutcome::default();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let (source, destination) = describe(&item);
+        let matched = if by_index {
+            info.get(index)
+        } else {
+            info.iter().find(|i| {
+                i.from.as_deref() == Some(source.as_str())
+                    || i.path.as_deref() == Some(source.as_str())
+            })
+        };
+
+        let item_errno = match matched {
+            Some(info) => info.errno.unwrap_or(0),
+            None => unmatched_errno,
+        };
+        let error = FileErrorKind::from_errno(item_errno);
+        let new_path = match error {
+            Some(_) => None,
+            None => matched
+                .and_then(|i| i.to.clone())
+                .or_else(|| destination.clone()),
+        };
+
+        let result = BatchItemResult {
+            item,
+            source,
+            destination,
+            new_path,
+            errno: item_errno,
+            error,
+        };
+        if result.error.is_some() {
+            outcome.failed.push(result);
+        } else {
+            outcome.succeeded.push(result);
+        }
+    }
+
+    outcome
+}
+
+/// 整个请求失败时，把其中的文件全部记为失败
+///
+/// 等待异步任务超时([`Error::TaskTimeout`])时任务可能仍在执行，文件记为结果未知而不是失败。
+fn failed_outcome<T>(
+    items: Vec<T>,
+    describe: impl Fn(&T) -> (String, Option<String>),
+    error: &Error,
+) -> BatchOutcome<T> {
+    if let Error::TaskTimeout { taskid } = *error {
+        let pending = items
+            .into_iter()
+            .map(|item| {
+                let (source, destination) = describe(&item);
+                PendingItem {
+                    item,
+                    source,
+                    destination,
+                    taskid,
+                }
+            })
+            .collect();
+        return BatchOutcome {
+            pending,
+            ..BatchOutcome::default()
+        };
+    }
+
+    let (errno, kind) = match error.errno() {
+        Some(errno) => (errno, FileErrorKind::from_errno(errno)),
+        None => (-1, None),
+    };
+
+    let failed = items
+        .into_iter()
+        .map(|item| {
+            let (source, destination) = describe(&item);
+            BatchItemResult {
+                item,
+                source,
+                destination,
+                new_path: None,
+                errno,
+                error: Some(kind.unwrap_or(FileErrorKind::RequestFailed)),
+            }
+        })
+        .collect();
+
+    BatchOutcome {
+        failed,
+        ..BatchOutcome::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(path: &str, errno: i32) -> FileOperationInfo {
+        FileOperationInfo {
+            path: Some(path.to_string()),
+            fs_id: None,
+            errno: Some(errno),
+            from: None,
+            to: None,
+        }
+    }
+
+    fn describe(path: &&str) -> (String, Option<String>) {
+        (path.to_string(), None)
+    }
+
+    #[test]
+    fn matches_by_index_when_counts_agree() {
+        // 数量一致时按顺序对应，即使路径与请求不同(如服务器返回规范化后的路径)
+        let outcome = collect_outcome(
+            vec!["/a", "/b"],
+            describe,
+            12,
+            vec![info("/A", 0), info("/B", -9)],
+        );
+        assert_eq!(outcome.succeeded.len(), 1);
+        assert_eq!(outcome.succeeded[0].source, "/a");
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].source, "/b");
+        assert_eq!(outcome.failed[0].error, Some(FileErrorKind::NotFound));
+    }
+
+    #[test]
+    fn matches_by_path_when_counts_differ() {
+        // 服务器只返回失败的文件，顺序与请求不同
+        let outcome = collect_outcome(
+            vec!["/a", "/b", "/c"],
+            describe,
+            12,
+            vec![info("/c", -9), info("/a", -7)],
+        );
+        let failed: Vec<_> = outcome
+            .failed
+            .iter()
+            .map(|r| (r.source.as_str(), r.errno))
+            .collect();
+        assert_eq!(failed, vec![("/a", -7), ("/c", -9)]);
+        // 部分失败时没有返回信息的文件视为成功
+        assert_eq!(outcome.succeeded.len(), 1);
+        assert_eq!(outcome.succeeded[0].source, "/b");
+        assert_eq!(outcome.succeeded[0].errno, 0);
+    }
+
+    #[test]
+    fn matches_by_source_path_and_reports_renamed_destination() {
+        let moved = FileOperationInfo {
+            path: None,
+            fs_id: None,
+            errno: Some(0),
+            from: Some("/a".to_string()),
+            to: Some("/dst/a(1)".to_string()),
+        };
+        let outcome = collect_outcome(
+            vec![("/a", "/dst/a"), ("/b", "/dst/b")],
+            |(from, to): &(&str, &str)| (from.to_string(), Some(to.to_string())),
+            0,
+            vec![moved],
+        );
+        assert_eq!(outcome.succeeded.len(), 2);
+        assert_eq!(outcome.succeeded[0].new_path.as_deref(), Some("/dst/a(1)"));
+        assert_eq!(outcome.succeeded[1].new_path.as_deref(), Some("/dst/b"));
+    }
+
+    #[test]
+    fn unmatched_items_use_overall_errno() {
+        let outcome = collect_outcome(vec!["/a"], describe, 0, Vec::new());
+        assert_eq!(outcome.succeeded.len(), 1);
+        assert_eq!(outcome.succeeded[0].errno, 0);
+
+        let outcome = collect_outcome(vec!["/a", "/b"], describe, -9, vec![info("/x", 0)]);
+        assert_eq!(outcome.failed.len(), 2);
+        assert!(outcome.failed.iter().all(|r| r.errno == -9));
+    }
+
+    #[test]
+    fn task_timeout_reports_items_as_pending() {
+        let outcome = failed_outcome(
+            vec!["/a", "/b"],
+            describe,
+            &Error::TaskTimeout { taskid: 42 },
+        );
+        assert!(outcome.succeeded.is_empty());
+        assert!(outcome.failed.is_empty());
+        assert_eq!(outcome.pending.len(), 2);
+        assert_eq!(outcome.pending_tasks(), vec![42]);
+        assert!(!outcome.is_success());
+
+        let outcome = failed_outcome(vec!["/a"], describe, &Error::Other("网络错误".into()));
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].error, Some(FileErrorKind::RequestFailed));
+    }
+}
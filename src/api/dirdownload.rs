@@ -0,0 +1,457 @@
+use crate::models::download::{
+    DownloadDirOptions, DownloadDirSummary, DownloadFailure, DownloadedFile, MtimeSource,
+    RenamedEntry,
+};
+use crate::models::file::FileInfo;
+use crate::models::path::ToRemotePath;
+use crate::{BaiduPanClient, Error, Result};
+use futures_util::{StreamExt, stream};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use unicode_normalization::UnicodeNormalization;
+
+/// 待下载的远程文件
+struct PendingFile {
+    info: FileInfo,
+    remote_path: String,
+    local_path: PathBuf,
+}
+
+impl BaiduPanClient {
+    /// 递归下载远程目录
+    ///
+    /// 通过 `file_list_all` 递归列出目录内容，在本地重建目录结构，
+    /// 批量预取下载链接后以有限并发下载文件。远程文件名中本地不允许的字符和保留名称
+    /// 会按 [`sanitize_file_name`] 的规则改名，改名后与同目录其他名称重复的在扩展名前追加
+    /// `~1`、`~2` 等序号，保证不会有两个文件写入同一个本地路径。改名记录在返回汇总的 `renamed` 中。
+    /// 单个文件下载失败不会中断整个任务，失败信息记录在返回的汇总中。
+    ///
+    /// # 参数
+    ///
+    /// * `remote_dir` - 远程目录
+    /// * `local_dir` - 本地目录
+    /// * `options` - 目录下载选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::DownloadDirOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let options = DownloadDirOptions::new().with_concurrency(4);
+    /// let summary = client.download_dir("/apps/myapp/backup", "./restore", &options).await?;
+    /// println!(
+    ///     "下载 {} 个，跳过 {} 个，失败 {} 个",
+    ///     summary.downloaded.len(),
+    ///     summary.skipped.len(),
+    ///     summary.failed.len()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_dir(
+        &self,
+        remote_dir: impl ToRemotePath,
+        local_dir: impl AsRef<Path>,
+        options: &DownloadDirOptions,
+    ) -> Result<DownloadDirSummary> {
+        let local_dir = local_dir.as_ref();
+        let remote_dir = remote_dir.to_remote_path()?;
+
+        let entries = self.list_all_recursive(&remote_dir).await?;
+        let prefix = remote_dir.as_str().trim_end_matches('/');
+
+        // 分页列表可能重复返回同一个条目，按路径去重
+        let mut seen = HashSet::new();
+        let entries: Vec<(FileInfo, String)> = entries
+            .into_iter()
+            .filter_map(|info| {
+                let relative = relative_path(info.path.as_deref()?, prefix)?;
+                (!relative.is_empty() && seen.insert(relative.clone())).then_some((info, relative))
+            })
+            .collect();
+        let relatives: Vec<&str> = entries.iter().map(|(_, r)| r.as_str()).collect();
+        let mappings = map_local_paths(local_dir, &relatives);
+
+        // 重建目录结构
+        tokio::fs::create_dir_all(local_dir).await?;
+        let mut summary = DownloadDirSummary::default();
+        let mut files = Vec::new();
+        for ((info, _), mapping) in entries.into_iter().zip(mappings) {
+            let Some(remote_path) = info.path.clone() else {
+                continue;
+            };
+            let LocalMapping {
+                local_path,
+                renamed,
+                deduplicated,
+            } = mapping;
+            if renamed {
+                summary.renamed.push(RenamedEntry {
+                    remote_path: remote_path.clone(),
+                    local_path: local_path.clone(),
+                    deduplicated,
+                });
+            }
+            if info.isdir == Some(1) {
+                tokio::fs::create_dir_all(&local_path).await?;
+            } else {
+                files.push(PendingFile {
+                    info,
+                    remote_path,
+                    local_path,
+                });
+            }
+        }
+
+        let mut pending = Vec::new();
+        for file in files {
+            if options.skip_existing
+                && is_up_to_date(
+                    &file.local_path,
+                    &file.info,
+                    options.download.preserve_times,
+                )
+                .await
+            {
+                summary.skipped.push(file.local_path);
+            } else {
+                pending.push(file);
+            }
+        }
+
+        // 批量预取下载链接，失败时由单个文件下载时重新获取
+        let fs_ids: Vec<i64> = pending.iter().filter_map(|f| f.info.fs_id).collect();
+        if let Err(e) = self.dlink_metas(&fs_ids).await
+            && self.config.debug
+        {
+            eprintln!("批量获取下载链接失败: {}", e);
+        }
+
+        let outcomes: Vec<std::result::Result<DownloadedFile, DownloadFailure>> =
+            stream::iter(pending)
+                .map(|file| self.download_dir_file(file, options))
+                .buffer_unordered(options.concurrency.max(1))
+                .collect()
+                .await;
+
+        for outcome in outcomes {
+            match outcome {
+                Ok(file) => summary.downloaded.push(file),
+                Err(failure) => summary.failed.push(failure),
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// 下载目录中的单个文件
+    async fn download_dir_file(
+        &self,
+        file: PendingFile,
+        options: &DownloadDirOptions,
+    ) -> std::result::Result<DownloadedFile, DownloadFailure> {
+        let result = match file.info.fs_id {
+            Some(fs_id) => {
+                self.download_fsid(fs_id, &file.local_path, &options.download)
+                    .await
+            }
+            None => Err(Error::Other(format!(
+                "未获取到文件 ID: {}",
+                file.remote_path
+            ))),
+        };
+
+        match result {
+            Ok(size) => Ok(DownloadedFile {
+                remote_path: file.remote_path,
+                local_path: file.local_path,
+                size,
+            }),
+            Err(error) => Err(DownloadFailure {
+                remote_path: file.remote_path,
+                local_path: file.local_path,
+                error,
+            }),
+        }
+    }
+}
+
+/// Windows 保留的设备名，不区分大小写，带扩展名时同样不可用
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 远程条目对应的本地路径
+#[derive(Debug, PartialEq, Eq)]
+struct LocalMapping {
+    /// 本地路径，不同条目的路径互不相同
+    local_path: PathBuf,
+    /// 本级名称被改写
+    renamed: bool,
+    /// 改写后与同目录其他名称重复，追加了序号
+    deduplicated: bool,
+}
+
+/// 服务器路径相对于 `prefix`(已规范化为 NFC 的目录路径)的部分，不在目录下时返回 None
+///
+/// 服务器可能按上传时的编码返回 NFD 形式的路径，先统一转换为 NFC 再比较。
+fn relative_path(path: &str, prefix: &str) -> Option<String> {
+    let path: String = path.nfc().collect();
+    Some(path.strip_prefix(prefix)?.strip_prefix('/')?.to_string())
+}
+
+/// 把远程相对路径映射为互不相同的本地路径
+///
+/// 逐级按 [`sanitize_file_name`] 处理本地不允许的文件名。不同的远程名称可能被改写为同一个
+/// 本地名称(如 `a:b.txt` 和 `a?b.txt` 都会变成 `a_b.txt`)，因此每个目录内先保留无需改写的
+/// 名称，再按远程路径顺序为改写后的名称分配位置，重名时在扩展名前追加 `~1`、`~2` ……。
+/// 同一组远程路径每次得到相同的结果。返回值与 `relatives` 一一对应。
+fn map_local_paths(local_dir: &Path, relatives: &[&str]) -> Vec<LocalMapping> {
+    // 父目录 -> [(下标, 名称)]，BTreeMap 保证父目录先于子目录处理
+    let mut children: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+    for (index, relative) in relatives.iter().enumerate() {
+        let relative = relative.trim_matches('/');
+        let (parent, name) = relative.rsplit_once('/').unwrap_or(("", relative));
+        children.entry(parent).or_default().push((index, name));
+    }
+
+    let mut dirs: HashMap<&str, PathBuf> = HashMap::new();
+    let mut mappings: Vec<Option<LocalMapping>> = relatives.iter().map(|_| None).collect();
+    for (parent, mut entries) in children {
+        let parent_path = match dirs.get(parent) {
+            Some(path) => path.clone(),
+            None => sanitize_relative(local_dir, parent),
+        };
+        entries.sort_by_key(|&(_, name)| name);
+
+        let mut used = HashSet::new();
+        let mut assigned = Vec::with_capacity(entries.len());
+        for &(index, name) in &entries {
+            if sanitize_file_name(name) == name {
+                used.insert(name.to_string());
+                assigned.push((index, name, name.to_string(), false));
+            }
+        }
+        for &(index, name) in &entries {
+            let sanitized = sanitize_file_name(name);
+            if sanitized != name {
+                let unique = unique_name(&sanitized, &used);
+                used.insert(unique.clone());
+                let deduplicated = unique != sanitized;
+                assigned.push((index, name, unique, deduplicated));
+            }
+        }
+
+        for (index, name, local_name, deduplicated) in assigned {
+            let local_path = parent_path.join(&local_name);
+            let relative = relatives[index].trim_matches('/');
+            let renamed = local_name != name;
+            dirs.insert(relative, local_path.clone());
+            mappings[index] = Some(LocalMapping {
+                local_path,
+                renamed,
+                deduplicated,
+            });
+        }
+    }
+
+    mappings.into_iter().flatten().collect()
+}
+
+/// 逐级处理相对路径中的文件名
+fn sanitize_relative(local_dir: &Path, relative: &str) -> PathBuf {
+    relative
+        .split('/')
+        .filter(|name| !name.is_empty())
+        .fold(local_dir.to_path_buf(), |path, name| {
+            path.join(sanitize_file_name(name))
+        })
+}
+
+/// 在已使用的名称中为 `name` 选择不重复的名称，如 `a.txt` -> `a~1.txt`
+fn unique_name(name: &str, used: &HashSet<String>) -> String {
+    if !used.contains(name) {
+        return name.to_string();
+    }
+
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    };
+    let mut n = 1;
+    loop {
+        let candidate = format!("{}~{}{}", stem, n, ext);
+        if !used.contains(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// 把服务器文件名(`server_filename`)转换为本地文件系统可用的名称
+///
+/// - 控制字符和 `<>:"/\|?*` 替换为 `_`
+/// - 结尾的点和空格替换为 `_`(因此 `.` 和 `..` 会变成 `_` 和 `__`)
+/// - Windows 保留名称(`CON`、`PRN`、`AUX`、`NUL`、`COM1`-`COM9`、`LPT1`-`LPT9`，
+///   不区分大小写，包括带扩展名的形式如 `con.txt`)在主名后追加 `_`，如 `CON_.txt`
+/// - 空名称替换为 `_`
+///
+/// 合法的名称原样返回。
+///
+/// # 示例
+/// ```
+/// use baidupan_sdk_rust::api::dirdownload::sanitize_file_name;
+///
+/// assert_eq!(sanitize_file_name("a:b?.txt"), "a_b_.txt");
+/// assert_eq!(sanitize_file_name("nul.tar.gz"), "nul_.tar.gz");
+/// assert_eq!(sanitize_file_name("报告.pdf"), "报告.pdf");
+/// ```
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let trimmed = sanitized.trim_end_matches(['.', ' ']);
+    let sanitized = format!("{}{}", trimmed, "_".repeat(sanitized.len() - trimmed.len()));
+
+    if sanitized.is_empty() {
+        return "_".to_string();
+    }
+
+    let stem_len = sanitized.find('.').unwrap_or(sanitized.len());
+    let stem = &sanitized[..stem_len];
+    if RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem)) {
+        return format!("{}_{}", stem, &sanitized[stem_len..]);
+    }
+
+    sanitized
+}
+
+/// 判断本地文件是否已是最新
+///
+/// 文件大小需一致。下载时设置了本地文件时间的，修改时间需等于设置的时间；
+/// 否则本地修改时间不早于服务器时视为最新。
+async fn is_up_to_date(
+    local_path: &Path,
+    info: &FileInfo,
+    preserve_times: Option<MtimeSource>,
+) -> bool {
+    let Ok(metadata) = tokio::fs::metadata(local_path).await else {
+        return false;
+    };
+    if Some(metadata.len() as i64) != info.size {
+        return false;
+    }
+
+    let local_mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64);
+    match preserve_times {
+        Some(source) => match (local_mtime, source.pick(info.mtime, info.local_mtime)) {
+            (Some(local), Some(expected)) => local == expected,
+            _ => false,
+        },
+        None => match (local_mtime, info.mtime) {
+            (Some(local), Some(remote)) => local >= remote,
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_path_normalizes_server_paths_to_nfc() {
+        // "é" 的 NFD 形式为 "e" + U+0301
+        let nfd = "/docs/cafe\u{301}/re\u{301}sume\u{301}.txt";
+        assert_eq!(
+            relative_path(nfd, "/docs/caf\u{e9}"),
+            Some("r\u{e9}sum\u{e9}.txt".to_string())
+        );
+        assert_eq!(
+            relative_path("/docs/a.txt", ""),
+            Some("docs/a.txt".to_string())
+        );
+        assert_eq!(relative_path("/docs2/a.txt", "/docs"), None);
+    }
+
+    fn local_paths(relatives: &[&str]) -> Vec<PathBuf> {
+        map_local_paths(Path::new("/dl"), relatives)
+            .into_iter()
+            .map(|m| m.local_path)
+            .collect()
+    }
+
+    #[test]
+    fn colliding_sanitized_names_get_unique_paths() {
+        let paths = local_paths(&["a?b.txt", "a:b.txt"]);
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/dl/a_b~1.txt"), PathBuf::from("/dl/a_b.txt")]
+        );
+    }
+
+    #[test]
+    fn unchanged_names_keep_priority_over_sanitized_ones() {
+        let mappings = map_local_paths(Path::new("/dl"), &["a:b.txt", "a_b.txt"]);
+        assert_eq!(mappings[1].local_path, PathBuf::from("/dl/a_b.txt"));
+        assert!(!mappings[1].renamed);
+        assert_eq!(mappings[0].local_path, PathBuf::from("/dl/a_b~1.txt"));
+        assert!(mappings[0].renamed && mappings[0].deduplicated);
+    }
+
+    #[test]
+    fn colliding_directories_keep_children_apart() {
+        let paths = local_paths(&["x:y", "x:y/f.txt", "x?y", "x?y/f.txt"]);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/dl/x_y"),
+                PathBuf::from("/dl/x_y/f.txt"),
+                PathBuf::from("/dl/x_y~1"),
+                PathBuf::from("/dl/x_y~1/f.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_entries_whose_own_name_changed_are_renamed() {
+        let mappings = map_local_paths(Path::new("/dl"), &["d:1", "d:1/ok.txt", "d?1", "d?1/a*b"]);
+        let flags: Vec<(bool, bool)> = mappings
+            .iter()
+            .map(|m| (m.renamed, m.deduplicated))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![(true, false), (false, false), (true, true), (true, false)]
+        );
+        assert_eq!(mappings[3].local_path, PathBuf::from("/dl/d_1~1/a_b"));
+    }
+
+    #[test]
+    fn unique_name_inserts_suffix_before_extension() {
+        let used: HashSet<String> = ["a.tar.gz", "a.tar~1.gz", ".env", "noext"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(unique_name("a.tar.gz", &used), "a.tar~2.gz");
+        assert_eq!(unique_name(".env", &used), ".env~1");
+        assert_eq!(unique_name("noext", &used), "noext~1");
+        assert_eq!(unique_name("free.txt", &used), "free.txt");
+    }
+}
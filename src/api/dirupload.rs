@@ -0,0 +1,377 @@
+use crate::api::fileupload::file_md5;
+use crate::models::download::Md5Check;
+use crate::models::file::LocalTimes;
+use crate::models::path::{RemotePath, ToRemotePath};
+use crate::models::upload::{
+    SymlinkPolicy, UploadDirOptions, UploadDirSummary, UploadFailure, UploadedFile,
+};
+use crate::{BaiduPanClient, Error, Result};
+use futures_util::{StreamExt, stream};
+use glob::Pattern;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 文件或目录不存在的错误码
+const ERRNO_NOT_FOUND: [i32; 2] = [-9, 31066];
+
+/// 待上传的本地文件
+struct LocalFile {
+    /// 本地绝对路径
+    path: PathBuf,
+    /// 相对于上传根目录的路径(使用 `/` 分隔)
+    relative: String,
+    /// 文件大小
+    size: u64,
+    /// 本地修改时间(Unix 秒)
+    mtime: Option<i64>,
+}
+
+/// 远程已存在的文件
+struct RemoteEntry {
+    /// 文件大小
+    size: u64,
+    /// 服务器返回的 MD5
+    md5: Option<String>,
+    /// 上传时记录的本地修改时间
+    local_mtime: Option<i64>,
+}
+
+/// 单个文件的上传结果
+enum FileOutcome {
+    Uploaded(UploadedFile),
+    RapidUploaded(UploadedFile),
+    Skipped(PathBuf),
+    Unverified(PathBuf),
+    Failed(UploadFailure),
+}
+
+impl BaiduPanClient {
+    /// 递归上传本地目录
+    ///
+    /// 遍历本地目录树，先在远程创建对应的目录结构，再以有限并发上传文件。
+    /// 单个文件读取或上传失败不会中断整个任务，失败信息记录在返回的汇总中。
+    ///
+    /// # 参数
+    ///
+    /// * `local_dir` - 本地目录
+    /// * `remote_dir` - 远程目录
+    /// * `options` - 目录上传选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::upload::UploadDirOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let options = UploadDirOptions::new()
+    ///     .exclude("*.tmp")
+    ///     .with_skip_existing(true)
+    ///     .with_concurrency(4);
+    /// let summary = client.upload_dir("./dist", "/apps/myapp/dist", &options).await?;
+    /// println!(
+    ///     "上传 {} 个，秒传 {} 个，跳过 {} 个，失败 {} 个",
+    ///     summary.uploaded.len(),
+    ///     summary.rapid_uploaded.len(),
+    ///     summary.skipped.len(),
+    ///     summary.failed.len()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_dir(
+        &self,
+        local_dir: impl AsRef<Path>,
+        remote_dir: impl ToRemotePath,
+        options: &UploadDirOptions,
+    ) -> Result<UploadDirSummary> {
+        let include = compile_patterns(&options.include)?;
+        let exclude = compile_patterns(&options.exclude)?;
+        let remote_dir = remote_dir.to_remote_path()?;
+
+        let tree = walk_local_dir(local_dir.as_ref(), &include, &exclude, options.symlinks).await?;
+
+        // 创建远程目录结构，已确认存在的目录不会重复请求
+        self.ensure_dir(&remote_dir).await?;
+        for dir in &tree.dirs {
+            self.ensure_dir(join_remote(&remote_dir, dir)).await?;
+        }
+
+        let remote_files = if options.skip_existing {
+            self.list_remote_files(&remote_dir).await?
+        } else {
+            HashMap::new()
+        };
+
+        let outcomes: Vec<FileOutcome> = stream::iter(tree.files)
+            .map(|file| {
+                let remote_path = join_remote(&remote_dir, &file.relative);
+                let existing = remote_files.get(&remote_path);
+                self.upload_dir_file(file, remote_path, existing, options)
+            })
+            .buffer_unordered(options.concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut summary = UploadDirSummary::default();
+        for (local_path, relative, error) in tree.failed {
+            summary.failed.push(UploadFailure {
+                local_path,
+                remote_path: join_remote(&remote_dir, &relative),
+                error,
+            });
+        }
+        for outcome in outcomes {
+            match outcome {
+                FileOutcome::Uploaded(file) => summary.uploaded.push(file),
+                FileOutcome::RapidUploaded(file) => summary.rapid_uploaded.push(file),
+                FileOutcome::Skipped(path) => summary.skipped.push(path),
+                FileOutcome::Unverified(path) => summary.unverified.push(path),
+                FileOutcome::Failed(failure) => summary.failed.push(failure),
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// 上传目录中的单个文件
+    async fn upload_dir_file(
+        &self,
+        file: LocalFile,
+        remote_path: String,
+        existing: Option<&RemoteEntry>,
+        options: &UploadDirOptions,
+    ) -> FileOutcome {
+        if let Some(remote) = existing
+            && remote.size == file.size
+        {
+            let content_md5 = remote
+                .md5
+                .as_deref()
+                .filter(|md5| Md5Check::is_content_hash(md5));
+            match content_md5 {
+                Some(md5) => match file_md5(&file.path).await {
+                    Ok(local_md5) if local_md5.eq_ignore_ascii_case(md5) => {
+                        return FileOutcome::Skipped(file.path);
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        return FileOutcome::Failed(UploadFailure {
+                            local_path: file.path,
+                            remote_path,
+                            error,
+                        });
+                    }
+                },
+                // 服务器 MD5 不是内容哈希时按上传时记录的本地修改时间判断
+                None => match (remote.local_mtime, file.mtime) {
+                    (Some(remote_mtime), Some(local_mtime)) if remote_mtime == local_mtime => {
+                        return FileOutcome::Skipped(file.path);
+                    }
+                    (Some(_), Some(_)) => {}
+                    _ => return FileOutcome::Unverified(file.path),
+                },
+            }
+        }
+
+        match self
+            .upload_file_inner(&file.path, &remote_path, &options.upload)
+            .await
+        {
+            Ok((response, rapid_upload)) => {
+                let uploaded = UploadedFile {
+                    local_path: file.path,
+                    remote_path,
+                    response,
+                };
+                if rapid_upload {
+                    FileOutcome::RapidUploaded(uploaded)
+                } else {
+                    FileOutcome::Uploaded(uploaded)
+                }
+            }
+            Err(error) => FileOutcome::Failed(UploadFailure {
+                local_path: file.path,
+                remote_path,
+                error,
+            }),
+        }
+    }
+
+    /// 递归列出远程目录下的文件，返回 路径 -> 文件信息
+    async fn list_remote_files(
+        &self,
+        remote_dir: &RemotePath,
+    ) -> Result<HashMap<String, RemoteEntry>> {
+        let list = match self.list_all_recursive(remote_dir).await {
+            Ok(list) => list,
+            Err(e) if e.errno().is_some_and(|n| ERRNO_NOT_FOUND.contains(&n)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut files = HashMap::new();
+        for info in list {
+            if info.isdir == Some(1) {
+                continue;
+            }
+            if let Some(path) = info.path {
+                let remote = RemoteEntry {
+                    size: info.size.unwrap_or(0).max(0) as u64,
+                    md5: info.md5,
+                    local_mtime: info.local_mtime,
+                };
+                files.insert(path, remote);
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+/// 编译 glob 规则
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|p| {
+            Pattern::new(p).map_err(|e| Error::ParamError(format!("无效的匹配规则 {}: {}", p, e)))
+        })
+        .collect()
+}
+
+/// 判断相对路径是否匹配任一规则
+///
+/// 不含 `/` 的规则同时匹配文件名，例如 `*.tmp` 可以排除任意层级的临时文件。
+fn matches_any(patterns: &[Pattern], relative: &str, name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|p| p.matches(relative) || (!p.as_str().contains('/') && p.matches(name)))
+}
+
+/// 拼接远程路径
+///
+/// 文件名会被规范化为 NFC，与服务器返回的路径一致。文件名不合法时原样拼接，
+/// 由上传请求报告该文件的错误，不影响其他文件。
+fn join_remote(remote_dir: &RemotePath, relative: &str) -> String {
+    match remote_dir.join(relative) {
+        Ok(path) => path.into(),
+        Err(_) => format!("{}/{}", remote_dir.as_str().trim_end_matches('/'), relative),
+    }
+}
+
+/// 遍历本地目录的结果(均为相对路径)
+#[derive(Default)]
+struct LocalTree {
+    /// 需要创建的目录
+    dirs: Vec<String>,
+    /// 需要上传的文件
+    files: Vec<LocalFile>,
+    /// 无法读取的文件或目录: (本地路径, 相对路径, 失败原因)
+    failed: Vec<(PathBuf, String, Error)>,
+}
+
+/// 遍历本地目录
+///
+/// 只有根目录无法读取时返回错误；其他无法读取的条目(文件名不是 UTF-8、失效的符号链接、
+/// 没有权限的子目录等)记录在结果中，继续遍历其余条目。
+async fn walk_local_dir(
+    root: &Path,
+    include: &[Pattern],
+    exclude: &[Pattern],
+    symlinks: SymlinkPolicy,
+) -> Result<LocalTree> {
+    let mut tree = LocalTree::default();
+    let mut visited = HashSet::new();
+    visited.insert(tokio::fs::canonicalize(root).await?);
+
+    let mut stack = vec![(root.to_path_buf(), String::new())];
+    while let Some((dir, dir_relative)) = stack.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if dir_relative.is_empty() => return Err(e.into()),
+            Err(e) => {
+                tree.failed.push((dir, dir_relative, e.into()));
+                continue;
+            }
+        };
+
+        loop {
+            let entry = match entries.next_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    tree.failed
+                        .push((dir.clone(), dir_relative.clone(), e.into()));
+                    break;
+                }
+            };
+
+            let path = entry.path();
+            let file_name = entry.file_name();
+            let name = file_name.to_string_lossy();
+            let relative = if dir_relative.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", dir_relative, name)
+            };
+
+            if file_name.to_str().is_none() {
+                let error =
+                    Error::ParamError(format!("文件名不是有效的 UTF-8: {}", path.display()));
+                tree.failed.push((path, relative, error));
+                continue;
+            }
+
+            if matches_any(exclude, &relative, &name) {
+                continue;
+            }
+
+            match classify_entry(&entry, &path, symlinks).await {
+                Ok(Some(metadata)) if metadata.is_dir() => {
+                    // 跟随符号链接时避免目录循环
+                    match tokio::fs::canonicalize(&path).await {
+                        Ok(canonical) => {
+                            if visited.insert(canonical) {
+                                tree.dirs.push(relative.clone());
+                                stack.push((path, relative));
+                            }
+                        }
+                        Err(e) => tree.failed.push((path, relative, e.into())),
+                    }
+                }
+                Ok(Some(metadata)) => {
+                    if metadata.is_file()
+                        && (include.is_empty() || matches_any(include, &relative, &name))
+                    {
+                        tree.files.push(LocalFile {
+                            path,
+                            relative,
+                            size: metadata.len(),
+                            mtime: LocalTimes::from_metadata(&metadata).mtime,
+                        });
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tree.failed.push((path, relative, e.into())),
+            }
+        }
+    }
+
+    // 保证父目录先于子目录创建
+    tree.dirs.sort();
+    Ok(tree)
+}
+
+/// 读取目录条目的元数据，按策略跳过符号链接时返回 None
+async fn classify_entry(
+    entry: &tokio::fs::DirEntry,
+    path: &Path,
+    symlinks: SymlinkPolicy,
+) -> std::io::Result<Option<std::fs::Metadata>> {
+    if entry.file_type().await?.is_symlink() {
+        match symlinks {
+            SymlinkPolicy::Skip => return Ok(None),
+            SymlinkPolicy::Follow => {}
+        }
+    }
+    tokio::fs::metadata(path).await.map(Some)
+}
@@ -1,15 +1,49 @@
+use crate::api::fileupload::file_md5;
 use crate::client::BaiduPanClient;
 use crate::error::{Error, Result};
-use std::path::Path;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use crate::models::download::{
+    ByteRange, CorruptFileAction, DownloadOptions, DownloadState, Md5Check, VerifyReport,
+};
+use crate::models::file::FileMetaInfo;
+use crate::models::path::ToRemotePath;
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt, TryStreamExt, stream};
+use reqwest::header::CONTENT_RANGE;
+use reqwest::{Response, StatusCode};
+use std::fs::FileTimes;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::Mutex;
 
 /// 下载 User-Agent，百度网盘要求设置该值
 const DOWNLOAD_USER_AGENT: &str = "pan.baidu.com";
 
+/// 断点续传临时文件后缀
+const PART_SUFFIX: &str = ".bdpart";
+
+/// 断点续传状态文件后缀
+const STATE_SUFFIX: &str = ".bdpart.state";
+
+/// 校验失败后隔离文件的后缀
+const CORRUPT_SUFFIX: &str = ".corrupt";
+
+/// 下载链接被拒绝后重新获取 dlink 的最大次数
+pub(crate) const MAX_DLINK_REFRESHES: u32 = 3;
+
+/// 断点续传状态文件，每完成一个分段写入一次
+struct StateFile {
+    path: PathBuf,
+    state: Mutex<DownloadState>,
+}
+
 impl BaiduPanClient {
     /// 下载文件到本地
     ///
+    /// 数据以流的方式写入文件，内存占用与文件大小无关。
+    ///
     /// 文件下载流程：
     /// 1. 通过 `file_metas` 接口（dlink=1）获取文件的 dlink 下载链接
     /// 2. 调用本接口传入 dlink 进行下载
@@ -24,9 +58,9 @@ impl BaiduPanClient {
     ///
     /// # 示例
     /// ```no_run
-    /// # use baidupan_sdk_rust::BaiduPanClient;
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = BaiduPanClient::new("access_token");
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
     ///
     /// // 先获取下载链接
     /// let metas = client.file_metas("[123456]", Some(1), None, None, None).await?;
@@ -43,8 +77,393 @@ impl BaiduPanClient {
         self.download_file_range(dlink, save_path, None, None).await
     }
 
+    /// 多连接分段下载文件
+    ///
+    /// 先通过 Range 请求获取文件大小并预分配本地文件，再把文件切分为多个分段并发下载，
+    /// 每个分段直接写入文件中对应的偏移位置。分段失败时只重试该分段，并从已写入的位置继续。
+    /// 下载完成后校验文件大小，设置了 `expected_size` 时同时与其比对。
+    ///
+    /// `options.verify` 开启时还会计算文件 MD5 并与 `options.expected_md5` 比对(不是内容哈希的
+    /// MD5 只校验大小)，校验失败时按 `options.on_corrupt` 处理文件并返回错误。
+    /// 开启校验但既没有设置 `expected_size` 也没有设置 `expected_md5` 时返回参数错误。
+    ///
+    /// 本方法没有服务器记录的文件时间，设置了 `options.preserve_times` 时返回参数错误，
+    /// 需要保留文件时间时请使用 [`BaiduPanClient::download_resumable`] 或
+    /// [`BaiduPanClient::download_fsid`]。
+    ///
+    /// # 参数
+    /// - `dlink`: 文件下载链接，通过 `file_metas` 接口获取
+    /// - `save_path`: 本地保存路径
+    /// - `options`: 下载选项(连接数、分段大小、重试次数、期望大小和 MD5)
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::DownloadOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let metas = client.file_metas("[123456]", Some(1), None, None, None).await?;
+    /// if let Some(file) = metas.list.first() {
+    ///     if let (Some(dlink), Some(size)) = (&file.dlink, file.size) {
+    ///         let options = DownloadOptions::new()
+    ///             .with_connections(8)
+    ///             .with_expected_size(size as u64);
+    ///         client.download_file_with_options(dlink, "/tmp/video.mp4", &options).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_file_with_options(
+        &self,
+        dlink: &str,
+        save_path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64> {
+        let save_path = save_path.as_ref();
+        if options.verify && options.expected_size.is_none() && options.expected_md5.is_none() {
+            return Err(Error::ParamError(
+                "校验下载文件需要设置 expected_size 或 expected_md5".to_string(),
+            ));
+        }
+        if options.preserve_times.is_some() {
+            return Err(Error::ParamError(
+                "只有 dlink 时无法获取文件时间，请使用 download_resumable 或 download_fsid 保留文件时间"
+                    .to_string(),
+            ));
+        }
+
+        let total = self.probe_download_size(dlink).await?;
+        if let Some(expected) = options.expected_size
+            && expected != total
+        {
+            return Err(Error::SizeMismatch {
+                expected,
+                actual: total,
+            });
+        }
+
+        // 预分配本地文件
+        let file = File::create(save_path).await.map_err(Error::IoError)?;
+        file.set_len(total).await.map_err(Error::IoError)?;
+        drop(file);
+
+        let segments = split_segments(0, total, options.segment_size);
+        let actual = self
+            .download_segments(dlink, save_path, segments, options, None, None)
+            .await?;
+        if actual != total {
+            if options.verify {
+                handle_corrupt(save_path, save_path, options.on_corrupt).await?;
+            }
+            return Err(Error::SizeMismatch {
+                expected: total,
+                actual,
+            });
+        }
+
+        if options.verify
+            && let Some(expected) = options.expected_md5.as_deref()
+        {
+            let md5 = file_md5(save_path).await?;
+            if let Err(e) = check_md5(Some(expected), md5) {
+                handle_corrupt(save_path, save_path, options.on_corrupt).await?;
+                return Err(e);
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// 可断点续传的文件下载
+    ///
+    /// 数据先写入 `<save_path>.bdpart` 临时文件，下载进度(已完成的字节范围)保存在
+    /// `<save_path>.bdpart.state` 中。再次调用时：
+    /// - 状态中的 fs_id、大小、MD5、修改时间与 `meta` 一致时，只下载缺失的范围
+    /// - 远程文件已变化或临时文件不完整时，丢弃旧数据重新下载
+    ///
+    /// 全部完成后把临时文件原子地重命名为 `save_path` 并删除状态文件。重试用尽后仍有分段数据不完整时
+    /// 返回 [`Error::SizeMismatch`]，已下载的部分保留在状态中供下次继续。
+    /// 设置了 `options.preserve_times` 时，再把文件的修改/访问时间设为服务器记录的时间。
+    ///
+    /// `options.verify` 开启时，重命名前会校验文件大小和 MD5：单连接且从头下载时边下载边计算 MD5，
+    /// 否则下载完成后读取临时文件计算。校验失败时按 `options.on_corrupt` 处理文件并返回
+    /// [`Error::SizeMismatch`] 或 [`Error::Md5Mismatch`]。
+    ///
+    /// # 参数
+    /// - `meta`: 通过 `file_metas`(dlink=1) 获取的文件元数据，需包含 `dlink`、`fs_id` 和 `size`
+    /// - `save_path`: 本地保存路径
+    /// - `options`: 下载选项(连接数、分段大小、重试次数)
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::DownloadOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let metas = client.file_metas("[123456]", Some(1), None, None, None).await?;
+    /// if let Some(meta) = metas.list.first() {
+    ///     // 中断后再次调用会从上次的进度继续
+    ///     client.download_resumable(meta, "/tmp/video.mp4", &DownloadOptions::new()).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_resumable(
+        &self,
+        meta: &FileMetaInfo,
+        save_path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64> {
+        let save_path = save_path.as_ref();
+        let dlink = meta.dlink.as_deref().ok_or_else(|| {
+            Error::ParamError("缺少 dlink，请使用 dlink=1 调用 file_metas".to_string())
+        })?;
+        let expected = DownloadState {
+            fs_id: meta
+                .fs_id
+                .ok_or_else(|| Error::ParamError("缺少 fs_id".to_string()))?,
+            size: meta
+                .size
+                .ok_or_else(|| Error::ParamError("缺少文件大小".to_string()))?
+                as u64,
+            md5: meta.md5.clone(),
+            mtime: meta.mtime,
+            completed: Vec::new(),
+        };
+
+        let part_path = sidecar_path(save_path, PART_SUFFIX);
+        let state_path = sidecar_path(save_path, STATE_SUFFIX);
+
+        // 只有远程文件未变化且临时文件完整时才沿用上次的进度
+        let part_len = tokio::fs::metadata(&part_path).await.ok().map(|m| m.len());
+        let resumed = match load_state(&state_path).await {
+            Some(saved) if saved.matches(&expected) && part_len == Some(expected.size) => {
+                Some(saved)
+            }
+            Some(_) => {
+                if self.config.debug {
+                    eprintln!(
+                        "远程文件已变化或临时文件不完整，重新下载: {}",
+                        save_path.display()
+                    );
+                }
+                None
+            }
+            None => None,
+        };
+
+        let state = match resumed {
+            Some(state) => state,
+            None => {
+                let file = File::create(&part_path).await.map_err(Error::IoError)?;
+                file.set_len(expected.size).await.map_err(Error::IoError)?;
+                save_state(&state_path, &expected).await?;
+                expected
+            }
+        };
+
+        let size = state.size;
+        // 单连接从头下载时分段按顺序写入，可以边下载边计算 MD5
+        let hasher = (options.verify && options.connections <= 1 && state.completed.is_empty())
+            .then(|| Mutex::new(md5::Context::new()));
+        let segments: Vec<(u64, u64)> = state
+            .missing()
+            .into_iter()
+            .flat_map(|(start, end)| split_segments(start, end + 1, options.segment_size))
+            .collect();
+        let tracker = StateFile {
+            path: state_path.clone(),
+            state: Mutex::new(state),
+        };
+
+        self.download_segments(
+            dlink,
+            &part_path,
+            segments,
+            options,
+            Some(&tracker),
+            hasher.as_ref(),
+        )
+        .await?;
+
+        // 临时文件已预分配，只能按状态中记录的已写入范围判断是否下载完整；
+        // 不完整时保留临时文件和状态，下次调用继续下载缺失的部分
+        let actual = tracker.state.into_inner().downloaded();
+        if actual != size {
+            return Err(Error::SizeMismatch {
+                expected: size,
+                actual,
+            });
+        }
+
+        if options.verify {
+            let md5 = match hasher {
+                Some(hasher) => format!("{:x}", hasher.into_inner().finalize()),
+                None => file_md5(&part_path).await?,
+            };
+            match check_integrity(meta, actual, md5) {
+                Ok(report) => {
+                    if let Md5Check::NotContentHash(server_md5) = &report.md5_check
+                        && self.config.debug
+                    {
+                        eprintln!(
+                            "服务器 MD5 不是文件内容哈希，仅校验了文件大小: {} ({})",
+                            save_path.display(),
+                            server_md5
+                        );
+                    }
+                }
+                Err(e) => {
+                    discard_corrupt(&part_path, &state_path, save_path, options.on_corrupt).await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        tokio::fs::rename(&part_path, save_path)
+            .await
+            .map_err(Error::IoError)?;
+        match tokio::fs::remove_file(&state_path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(Error::IoError(e)),
+            _ => {}
+        }
+
+        if let Some(source) = options.preserve_times
+            && let Some(mtime) = source.pick(meta.mtime, meta.local_mtime)
+        {
+            set_file_times(save_path, mtime).await?;
+        }
+
+        Ok(size)
+    }
+
+    /// 校验本地文件与服务器文件是否一致
+    ///
+    /// 比较文件大小和 MD5。服务器返回的 MD5 不是文件内容哈希时(见 [`Md5Check::is_content_hash`])
+    /// 只校验大小，并在结果中标记为 [`Md5Check::NotContentHash`]。本方法不会修改本地文件。
+    ///
+    /// # 参数
+    /// - `local_path`: 本地文件路径
+    /// - `meta`: 通过 `file_metas` 获取的文件元数据
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let meta = client.dlink_meta(123456).await?;
+    /// let report = client.verify_file("/tmp/video.mp4", &meta).await?;
+    /// println!("校验通过: {:?}", report.md5_check);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn verify_file(
+        &self,
+        local_path: impl AsRef<Path>,
+        meta: &FileMetaInfo,
+    ) -> Result<VerifyReport> {
+        let local_path = local_path.as_ref();
+        let size = tokio::fs::metadata(local_path)
+            .await
+            .map_err(Error::IoError)?
+            .len();
+        if let Some(expected) = meta.size
+            && expected as u64 != size
+        {
+            return Err(Error::SizeMismatch {
+                expected: expected as u64,
+                actual: size,
+            });
+        }
+
+        let md5 = file_md5(local_path).await?;
+        check_integrity(meta, size, md5)
+    }
+
+    /// 根据文件 ID 下载文件
+    ///
+    /// 自动获取并缓存 dlink(见 [`BaiduPanClient::dlink_meta`])，以可断点续传的方式下载。
+    /// 下载过程中 dlink 过期或被拒绝时，会重新获取 dlink 并从已完成的进度继续。
+    ///
+    /// # 参数
+    /// - `fs_id`: 文件 ID
+    /// - `save_path`: 本地保存路径
+    /// - `options`: 下载选项
+    pub async fn download_fsid(
+        &self,
+        fs_id: i64,
+        save_path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64> {
+        let save_path = save_path.as_ref();
+        let mut refreshes = 0;
+
+        loop {
+            let meta = self.dlink_meta(fs_id).await?;
+            match self.download_resumable(&meta, save_path, options).await {
+                Err(e) if is_dlink_rejected(&e) && refreshes < MAX_DLINK_REFRESHES => {
+                    refreshes += 1;
+                    if self.config.debug {
+                        eprintln!("dlink 已失效，重新获取后继续下载: {}", e);
+                    }
+                    self.invalidate_dlink(fs_id);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// 根据远程路径下载文件
+    ///
+    /// 先通过 [`BaiduPanClient::file_info_by_path`] 解析出 fs_id，再调用
+    /// [`BaiduPanClient::download_fsid`] 下载。
+    ///
+    /// # 参数
+    /// - `remote_path`: 远程文件路径
+    /// - `save_path`: 本地保存路径
+    /// - `options`: 下载选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::DownloadOptions;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// client
+    ///     .download_path("/apps/myapp/video.mp4", "/tmp/video.mp4", &DownloadOptions::new())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_path(
+        &self,
+        remote_path: impl ToRemotePath,
+        save_path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64> {
+        let remote_path = remote_path.to_remote_path()?;
+        let info = self.file_info_by_path(&remote_path).await?;
+        if info.isdir == Some(1) {
+            return Err(Error::ParamError(format!("不能下载目录: {}", remote_path)));
+        }
+        let fs_id = info
+            .fs_id
+            .ok_or_else(|| Error::Other(format!("未获取到文件 ID: {}", remote_path)))?;
+
+        self.download_fsid(fs_id, save_path, options).await
+    }
+
     /// 下载文件的指定字节范围（支持断点续传）
     ///
+    /// `range_start` 大于 0 时，本地文件的长度必须不小于 `range_start`，
+    /// 超出部分会被截断后从 `range_start` 继续写入；本地文件过短时返回错误，避免写出错位的数据。
+    /// 需要自动记录进度时请使用 [`BaiduPanClient::download_resumable`]。
+    ///
     /// # 参数
     /// - `dlink`: 文件下载链接，通过 `file_metas` 接口获取
     /// - `save_path`: 本地保存路径
@@ -53,9 +472,9 @@ impl BaiduPanClient {
     ///
     /// # 示例
     /// ```no_run
-    /// # use baidupan_sdk_rust::BaiduPanClient;
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = BaiduPanClient::new("access_token");
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
     ///
     /// // 下载文件的前 1MB
     /// client.download_file_range(
@@ -74,30 +493,124 @@ impl BaiduPanClient {
         range_start: Option<u64>,
         range_end: Option<u64>,
     ) -> Result<u64> {
-        let bytes: Vec<u8> = self.download_bytes(dlink, range_start, range_end).await?;
-        let written = bytes.len() as u64;
-
-        // 如果有断点续传起始位置，则以追加模式打开文件
-        let mut file = if range_start.is_some() && range_start != Some(0) {
-            tokio::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(save_path)
-                .await
-                .map_err(Error::IoError)?
-        } else {
-            File::create(save_path).await.map_err(Error::IoError)?
+        // 有断点续传起始位置时，本地文件必须恰好包含起始位置之前的数据
+        let mut file = match range_start {
+            Some(start) if start > 0 => {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .truncate(false)
+                    .open(save_path)
+                    .await
+                    .map_err(Error::IoError)?;
+                let local_len = file.metadata().await.map_err(Error::IoError)?.len();
+                if local_len < start {
+                    return Err(Error::ParamError(format!(
+                        "本地文件只有 {} 字节，无法从第 {} 字节续传",
+                        local_len, start
+                    )));
+                }
+                // 丢弃起始位置之后的旧数据，从起始位置继续写入
+                file.set_len(start).await.map_err(Error::IoError)?;
+                file.seek(SeekFrom::Start(start))
+                    .await
+                    .map_err(Error::IoError)?;
+                file
+            }
+            _ => File::create(save_path).await.map_err(Error::IoError)?,
         };
 
-        file.write_all(&bytes).await.map_err(Error::IoError)?;
-        file.flush().await.map_err(Error::IoError)?;
+        let range = (range_start.is_some() || range_end.is_some()).then_some(ByteRange {
+            start: range_start,
+            end: range_end,
+        });
+        self.download_to_writer(dlink, &mut file, range).await
+    }
 
+    /// 流式下载并写入 `AsyncWrite`
+    ///
+    /// 数据按到达顺序逐块写入，不会在内存中缓存整个文件，适合下载大文件。
+    ///
+    /// # 参数
+    /// - `dlink`: 文件下载链接
+    /// - `writer`: 写入目标
+    /// - `range`: 下载的字节范围，None 表示整个文件
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let mut file = tokio::fs::File::create("/tmp/video.mp4").await?;
+    /// let written = client
+    ///     .download_to_writer("https://d.pcs.baidu.com/...", &mut file, None)
+    ///     .await?;
+    /// println!("下载了 {} 字节", written);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_to_writer<W>(
+        &self,
+        dlink: &str,
+        writer: &mut W,
+        range: Option<ByteRange>,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut stream = Box::pin(self.download_stream(dlink, range).await?);
+        let mut written = 0u64;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await.map_err(Error::IoError)?;
+            written += chunk.len() as u64;
+        }
+
+        writer.flush().await.map_err(Error::IoError)?;
         Ok(written)
     }
 
+    /// 以字节流的形式下载文件
+    ///
+    /// 返回的流在数据到达时逐块产出，内存占用与文件大小无关。
+    ///
+    /// # 参数
+    /// - `dlink`: 文件下载链接
+    /// - `range`: 下载的字节范围，None 表示整个文件
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::ByteRange;
+    /// # use futures_util::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let range = ByteRange::new(0, 1024 * 1024 - 1);
+    /// let mut stream = Box::pin(client.download_stream("https://d.pcs.baidu.com/...", Some(range)).await?);
+    /// while let Some(chunk) = stream.next().await {
+    ///     println!("收到 {} 字节", chunk?.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_stream(
+        &self,
+        dlink: &str,
+        range: Option<ByteRange>,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Send + use<>> {
+        let response = self.send_download_request(dlink, range).await?;
+        Ok(response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(Error::HttpError)))
+    }
+
     /// 下载文件内容为字节数组
     ///
     /// 与 `download_file` 类似，但返回字节数组而不是写入磁盘，便于自定义处理。
+    /// 整个响应会缓存在内存中，大文件请使用 `download_stream` 或 `download_to_writer`。
     ///
     /// # 参数
     /// - `dlink`: 文件下载链接
@@ -106,9 +619,9 @@ impl BaiduPanClient {
     ///
     /// # 示例
     /// ```no_run
-    /// # use baidupan_sdk_rust::BaiduPanClient;
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = BaiduPanClient::new("access_token");
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
     /// let bytes = client.download_bytes("https://d.pcs.baidu.com/...", None, None).await?;
     /// println!("下载了 {} 字节", bytes.len());
     /// # Ok(())
@@ -120,6 +633,217 @@ impl BaiduPanClient {
         range_start: Option<u64>,
         range_end: Option<u64>,
     ) -> Result<Vec<u8>> {
+        let range = (range_start.is_some() || range_end.is_some()).then_some(ByteRange {
+            start: range_start,
+            end: range_end,
+        });
+        let response = self.send_download_request(dlink, range).await?;
+
+        let bytes = response.bytes().await.map_err(Error::HttpError)?;
+        Ok(bytes.to_vec())
+    }
+
+    /// 下载 `[start, end]` 范围的数据，要求服务器返回完整的 206 响应
+    pub(crate) async fn download_range_bytes(
+        &self,
+        dlink: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Bytes> {
+        let response = self
+            .send_download_request(dlink, Some(ByteRange::new(start, end)))
+            .await?;
+
+        if response.status() != StatusCode::PARTIAL_CONTENT && start != 0 {
+            return Err(Error::Other("服务器不支持 Range 请求".to_string()));
+        }
+
+        // 服务器忽略 Range 返回 200 时响应是整个文件，读够所需的字节后即停止
+        let expected = (end - start + 1) as usize;
+        let mut buffer = BytesMut::with_capacity(expected);
+        let mut stream = response.bytes_stream();
+        while buffer.len() < expected {
+            let Some(chunk) = stream.next().await else {
+                break;
+            };
+            let chunk = chunk.map_err(Error::HttpError)?;
+            let take = chunk.len().min(expected - buffer.len());
+            buffer.extend_from_slice(&chunk[..take]);
+        }
+
+        if buffer.len() < expected {
+            return Err(Error::Other(format!(
+                "范围数据不完整: 期望 {} 字节, 实际 {} 字节",
+                expected,
+                buffer.len()
+            )));
+        }
+        Ok(buffer.freeze())
+    }
+
+    /// 通过 `Range: bytes=0-0` 请求获取远程文件大小
+    async fn probe_download_size(&self, dlink: &str) -> Result<u64> {
+        let response = match self
+            .send_download_request(dlink, Some(ByteRange::new(0, 0)))
+            .await
+        {
+            Ok(response) => response,
+            // 空文件无法满足任何 Range 请求
+            Err(e) if e.errno() == Some(416) => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        // 206 响应从 Content-Range(bytes 0-0/总大小) 中解析，否则使用 Content-Length
+        let total = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit('/').next())
+            .and_then(|value| value.parse().ok())
+            .or_else(|| {
+                (response.status() == StatusCode::OK)
+                    .then(|| response.content_length())
+                    .flatten()
+            });
+
+        total.ok_or_else(|| Error::Other("无法获取远程文件大小".to_string()))
+    }
+
+    /// 并发下载多个分段，把每个分段实际写入的范围记录到状态文件(如果有)，返回写入的总字节数
+    ///
+    /// 重试用尽后仍未写满的分段只记录已写入的部分，调用方应比较返回值与期望大小。
+    /// 传入 `hasher` 时写入的数据会同时计算 MD5，调用方需保证分段按顺序下载(单连接)。
+    async fn download_segments(
+        &self,
+        dlink: &str,
+        save_path: &Path,
+        segments: Vec<(u64, u64)>,
+        options: &DownloadOptions,
+        state: Option<&StateFile>,
+        hasher: Option<&Mutex<md5::Context>>,
+    ) -> Result<u64> {
+        let written = stream::iter(segments)
+            .map(|(start, end)| async move {
+                let written = self
+                    .download_segment(dlink, save_path, start, end, options, hasher)
+                    .await?;
+                if written != end - start + 1 && self.config.debug {
+                    eprintln!(
+                        "分段 {}-{} 数据不完整: 期望 {} 字节, 实际 {} 字节",
+                        start,
+                        end,
+                        end - start + 1,
+                        written
+                    );
+                }
+                if let Some(state) = state
+                    && written > 0
+                {
+                    state.record(start, start + written - 1).await?;
+                }
+                Ok::<_, Error>(written)
+            })
+            .buffer_unordered(options.connections.max(1))
+            .try_collect::<Vec<u64>>()
+            .await?;
+        Ok(written.into_iter().sum())
+    }
+
+    /// 下载一个分段并写入文件的对应位置，返回实际写入的字节数
+    ///
+    /// 请求失败或响应数据不足时从已写入的位置重试，重试用尽后：请求失败返回错误，
+    /// 数据不足返回已写入的字节数。
+    async fn download_segment(
+        &self,
+        dlink: &str,
+        save_path: &Path,
+        start: u64,
+        end: u64,
+        options: &DownloadOptions,
+        hasher: Option<&Mutex<md5::Context>>,
+    ) -> Result<u64> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(save_path)
+            .await
+            .map_err(Error::IoError)?;
+        let mut position = start;
+        let mut attempt = 0;
+
+        loop {
+            let result = self
+                .copy_range(dlink, &mut file, &mut position, end, hasher)
+                .await;
+            let reason = match result {
+                Ok(()) if position > end => return Ok(position - start),
+                Ok(()) => format!("数据不完整，已写到 {} 字节", position),
+                Err(e) if attempt < options.max_retries => e.to_string(),
+                Err(e) => return Err(e),
+            };
+            if attempt >= options.max_retries {
+                return Ok(position - start);
+            }
+
+            attempt += 1;
+            if self.config.debug {
+                eprintln!(
+                    "分段 {}-{} 下载失败，第 {} 次重试: {}",
+                    start, end, attempt, reason
+                );
+            }
+            tokio::time::sleep(options.backoff(attempt)).await;
+        }
+    }
+
+    /// 下载 `[position, end]` 并写入文件的对应位置，`position` 随写入前进
+    ///
+    /// 响应提前结束时正常返回，由调用方根据 `position` 判断是否写满。
+    async fn copy_range(
+        &self,
+        dlink: &str,
+        file: &mut File,
+        position: &mut u64,
+        end: u64,
+        hasher: Option<&Mutex<md5::Context>>,
+    ) -> Result<()> {
+        let response = self
+            .send_download_request(dlink, Some(ByteRange::new(*position, end)))
+            .await?;
+
+        // 服务器忽略 Range 时返回的是整个文件，只有从 0 开始才能直接使用
+        if response.status() != StatusCode::PARTIAL_CONTENT && *position != 0 {
+            return Err(Error::Other("服务器不支持 Range 请求".to_string()));
+        }
+
+        file.seek(SeekFrom::Start(*position))
+            .await
+            .map_err(Error::IoError)?;
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(Error::HttpError)?;
+            let remaining = (end + 1 - *position) as usize;
+            let data = &chunk[..chunk.len().min(remaining)];
+            file.write_all(data).await.map_err(Error::IoError)?;
+            if let Some(hasher) = hasher {
+                hasher.lock().await.consume(data);
+            }
+            *position += data.len() as u64;
+            if *position > end {
+                break;
+            }
+        }
+        file.flush().await.map_err(Error::IoError)?;
+
+        Ok(())
+    }
+
+    /// 发送下载请求并检查响应状态
+    async fn send_download_request(
+        &self,
+        dlink: &str,
+        range: Option<ByteRange>,
+    ) -> Result<Response> {
         // 将 access_token 拼接到 dlink URL
         let mut url = url::Url::parse(dlink)?;
         url.query_pairs_mut()
@@ -136,9 +860,8 @@ impl BaiduPanClient {
             .header("User-Agent", DOWNLOAD_USER_AGENT);
 
         // 添加 Range 头以支持断点续传
-        if range_start.is_some() || range_end.is_some() {
-            let range = build_range_header(range_start, range_end);
-            request = request.header("Range", range);
+        if let Some(range) = range {
+            request = request.header("Range", range.header_value());
         }
 
         let response = request.send().await.map_err(Error::HttpError)?;
@@ -152,22 +875,290 @@ impl BaiduPanClient {
             });
         }
 
-        let bytes = response.bytes().await.map_err(Error::HttpError)?;
-        Ok(bytes.to_vec())
+        Ok(response)
+    }
+}
+
+/// 是否为 dlink 过期或被拒绝导致的下载失败
+pub(crate) fn is_dlink_rejected(error: &Error) -> bool {
+    matches!(error.errno(), Some(401 | 403 | 404 | 410))
+}
+
+/// 用服务器元数据校验已下载文件的大小和 MD5
+fn check_integrity(meta: &FileMetaInfo, size: u64, md5: String) -> Result<VerifyReport> {
+    if let Some(expected) = meta.size
+        && expected as u64 != size
+    {
+        return Err(Error::SizeMismatch {
+            expected: expected as u64,
+            actual: size,
+        });
+    }
+
+    let md5_check = check_md5(meta.md5.as_deref(), md5.clone())?;
+    Ok(VerifyReport {
+        size,
+        md5,
+        md5_check,
+    })
+}
+
+/// 比较本地 MD5 与服务器 MD5
+fn check_md5(expected: Option<&str>, md5: String) -> Result<Md5Check> {
+    match expected {
+        None | Some("") => Ok(Md5Check::Unavailable),
+        Some(server) if !Md5Check::is_content_hash(server) => {
+            Ok(Md5Check::NotContentHash(server.to_string()))
+        }
+        Some(server) if server.eq_ignore_ascii_case(&md5) => Ok(Md5Check::Matched),
+        Some(server) => Err(Error::Md5Mismatch {
+            expected: server.to_ascii_lowercase(),
+            actual: md5,
+        }),
     }
 }
 
-/// 构建 Range 请求头字符串
-///
-/// - `Some(start), Some(end)` → `bytes=start-end`
-/// - `Some(start), None`      → `bytes=start-`
-/// - `None, Some(end)`        → `bytes=-end`
-/// - `None, None`             → `bytes=0-`
-fn build_range_header(start: Option<u64>, end: Option<u64>) -> String {
-    match (start, end) {
-        (Some(s), Some(e)) => format!("bytes={}-{}", s, e),
-        (Some(s), None) => format!("bytes={}-", s),
-        (None, Some(e)) => format!("bytes=-{}", e),
-        (None, None) => "bytes=0-".to_string(),
+/// 按 `action` 处理校验失败的临时文件，并删除状态文件使下次重新下载
+async fn discard_corrupt(
+    part_path: &Path,
+    state_path: &Path,
+    save_path: &Path,
+    action: CorruptFileAction,
+) -> Result<()> {
+    handle_corrupt(part_path, save_path, action).await?;
+    match tokio::fs::remove_file(state_path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::IoError(e)),
+        _ => Ok(()),
+    }
+}
+
+/// 按 `action` 处理校验失败的文件 `path`，`save_path` 为它的目标路径
+async fn handle_corrupt(path: &Path, save_path: &Path, action: CorruptFileAction) -> Result<()> {
+    match action {
+        CorruptFileAction::Delete => tokio::fs::remove_file(path).await?,
+        CorruptFileAction::Quarantine => {
+            tokio::fs::rename(path, sidecar_path(save_path, CORRUPT_SUFFIX)).await?
+        }
+        CorruptFileAction::Keep if path != save_path => tokio::fs::rename(path, save_path).await?,
+        CorruptFileAction::Keep => {}
+    }
+    Ok(())
+}
+
+/// 把本地文件的修改时间和访问时间设置为 `timestamp`(Unix 秒)
+async fn set_file_times(path: &Path, timestamp: i64) -> Result<()> {
+    let time = UNIX_EPOCH + Duration::from_secs(timestamp.max(0) as u64);
+    let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await
+        .map_err(Error::IoError)?
+        .into_std()
+        .await;
+    file.set_times(FileTimes::new().set_accessed(time).set_modified(time))
+        .map_err(Error::IoError)
+}
+
+/// 把 `[start, end)` 切分为若干个 `[start, end]` 分段
+fn split_segments(start: u64, end: u64, segment_size: u64) -> Vec<(u64, u64)> {
+    let segment_size = segment_size.max(1);
+    (start..end)
+        .step_by(segment_size as usize)
+        .map(|s| (s, (s + segment_size).min(end) - 1))
+        .collect()
+}
+
+/// 在文件路径后追加后缀，如 `a.mp4` -> `a.mp4.bdpart`
+fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 读取断点续传状态，文件不存在或无法解析时返回 None
+async fn load_state(path: &Path) -> Option<DownloadState> {
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+/// 写入断点续传状态(先写临时文件再重命名，避免中断时留下损坏的状态)
+async fn save_state(path: &Path, state: &DownloadState) -> Result<()> {
+    let tmp_path = sidecar_path(path, ".tmp");
+    tokio::fs::write(&tmp_path, serde_json::to_vec(state)?)
+        .await
+        .map_err(Error::IoError)?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(Error::IoError)
+}
+
+impl StateFile {
+    /// 记录已完成的分段并保存
+    async fn record(&self, start: u64, end: u64) -> Result<()> {
+        let mut state = self.state.lock().await;
+        state.mark_completed(start, end);
+        save_state(&self.path, &state).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_segments_covers_range_exactly() {
+        assert_eq!(split_segments(0, 25, 10), vec![(0, 9), (10, 19), (20, 24)]);
+        assert_eq!(split_segments(5, 15, 10), vec![(5, 14)]);
+        assert_eq!(split_segments(0, 20, 10), vec![(0, 9), (10, 19)]);
+    }
+
+    #[test]
+    fn check_md5_compares_only_content_hashes() {
+        let md5 = "d41d8cd98f00b204e9800998ecf8427e".to_string();
+        assert_eq!(
+            check_md5(Some("D41D8CD98F00B204E9800998ECF8427E"), md5.clone()).unwrap(),
+            Md5Check::Matched
+        );
+        assert_eq!(check_md5(None, md5.clone()).unwrap(), Md5Check::Unavailable);
+        assert!(matches!(
+            check_md5(Some("a1b2c3"), md5.clone()).unwrap(),
+            Md5Check::NotContentHash(_)
+        ));
+        assert!(matches!(
+            check_md5(Some("00000000000000000000000000000000"), md5),
+            Err(Error::Md5Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn split_segments_handles_empty_and_zero_size() {
+        assert!(split_segments(10, 10, 4).is_empty());
+        assert_eq!(split_segments(0, 3, 0), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn split_segments_of_missing_ranges_skip_completed_bytes() {
+        let mut state = DownloadState {
+            fs_id: 1,
+            size: 100,
+            md5: None,
+            mtime: None,
+            completed: Vec::new(),
+        };
+        state.mark_completed(30, 59);
+
+        let segments: Vec<(u64, u64)> = state
+            .missing()
+            .into_iter()
+            .flat_map(|(start, end)| split_segments(start, end + 1, 25))
+            .collect();
+        assert_eq!(segments, vec![(0, 24), (25, 29), (60, 84), (85, 99)]);
+    }
+
+    /// 启动一个本地 HTTP 服务，每个 Range 请求最多只返回 `chunk` 字节
+    fn short_range_server(size: u64, chunk: u64) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                let (start, end) = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes="))
+                    .and_then(|range| range.trim().split_once('-'))
+                    .map(|(s, e)| (s.parse::<u64>().unwrap(), e.parse::<u64>().unwrap()))
+                    .unwrap();
+                let end = end.min(start + chunk - 1);
+                let body = vec![b'x'; (end - start + 1) as usize];
+                let header = format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    start,
+                    end,
+                    size,
+                    body.len()
+                );
+                stream.write_all(header.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        format!("http://{}/file", addr)
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("baidupan-{}-{}", std::process::id(), name))
+    }
+
+    #[tokio::test]
+    async fn short_segment_fails_with_size_mismatch() {
+        let dlink = short_range_server(10, 4);
+        let client = BaiduPanClient::new(crate::Config::new("token"));
+        let save_path = temp_path("short-segment");
+        let options = DownloadOptions::new()
+            .with_connections(1)
+            .with_segment_size(10)
+            .with_max_retries(1)
+            .with_retry_delay(Duration::ZERO);
+
+        let result = client
+            .download_file_with_options(&dlink, &save_path, &options)
+            .await;
+        let _ = std::fs::remove_file(&save_path);
+        assert!(matches!(
+            result,
+            Err(Error::SizeMismatch {
+                expected: 10,
+                actual: 8
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn resumable_short_segment_keeps_partial_progress() {
+        let dlink = short_range_server(10, 4);
+        let client = BaiduPanClient::new(crate::Config::new("token"));
+        let save_path = temp_path("resumable-short-segment");
+        let meta: FileMetaInfo = serde_json::from_value(serde_json::json!({
+            "fs_id": 1,
+            "size": 10,
+            "dlink": dlink,
+        }))
+        .unwrap();
+        let options = DownloadOptions::new()
+            .with_connections(1)
+            .with_segment_size(10)
+            .with_max_retries(1)
+            .with_retry_delay(Duration::ZERO)
+            .with_verify(true);
+
+        let result = client.download_resumable(&meta, &save_path, &options).await;
+        let part_path = sidecar_path(&save_path, PART_SUFFIX);
+        let state_path = sidecar_path(&save_path, STATE_SUFFIX);
+        let state = load_state(&state_path).await;
+        let saved = save_path.exists();
+        let _ = std::fs::remove_file(&part_path);
+        let _ = std::fs::remove_file(&state_path);
+        let _ = std::fs::remove_file(&save_path);
+
+        assert!(matches!(
+            result,
+            Err(Error::SizeMismatch {
+                expected: 10,
+                actual: 8
+            })
+        ));
+        assert!(!saved);
+        assert_eq!(state.unwrap().completed, vec![(0, 7)]);
     }
 }
@@ -1,6 +1,11 @@
-use crate::models::file::FileListResponse;
-use crate::{BaiduPanClient, Result};
+use crate::models::file::{FileInfo, FileListResponse, ListOptions};
+use crate::models::path::ToRemotePath;
+use crate::{BaiduPanClient, Error, Result};
+use futures_util::future::Either;
+use futures_util::{Stream, StreamExt, TryStreamExt, stream};
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
 
 impl BaiduPanClient {
     /// 获取文件列表
@@ -14,7 +19,7 @@ impl BaiduPanClient {
     /// * `limit` - 返回数量
     pub async fn file_list(
         &self,
-        dir: &str,
+        dir: impl ToRemotePath,
         order: Option<&str>,
         desc: Option<i32>,
         start: Option<i32>,
@@ -24,7 +29,7 @@ impl BaiduPanClient {
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "list".to_string());
-        params.insert("dir".to_string(), dir.to_string());
+        params.insert("dir".to_string(), dir.to_remote_path()?.into());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
         if let Some(order) = order {
@@ -44,6 +49,67 @@ impl BaiduPanClient {
         BaiduPanClient::parse_response(response).await
     }
 
+    /// 逐项列出目录中的文件，自动翻页
+    ///
+    /// 返回的流按需请求下一页，读到最后一页(数量不足一页)时结束。
+    /// 请求失败时流返回该错误后结束。
+    ///
+    /// # 参数
+    ///
+    /// * `dir` - 目录路径
+    /// * `options` - 列表选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::file::ListOptions;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let options = ListOptions::new().with_order("time").with_desc(true);
+    /// let mut files = std::pin::pin!(client.file_list_stream("/apps/myapp", &options));
+    /// while let Some(file) = files.try_next().await? {
+    ///     println!("{:?}", file.path);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn file_list_stream(
+        &self,
+        dir: impl ToRemotePath,
+        options: &ListOptions,
+    ) -> impl Stream<Item = Result<FileInfo>> + 'static {
+        let dir = match dir.to_remote_path() {
+            Ok(dir) => dir,
+            Err(e) => return Either::Left(stream::once(async { Err(e) })),
+        };
+        let client = self.clone();
+        let options = options.clone();
+        let page_size = options.page_limit();
+
+        Either::Right(paginate(move |start| {
+            let client = client.clone();
+            let dir = dir.clone();
+            let options = options.clone();
+            async move {
+                let page = client
+                    .file_list(
+                        &dir,
+                        options.order.as_deref(),
+                        Some(options.desc as i32),
+                        Some(start),
+                        Some(page_size),
+                    )
+                    .await?;
+                let count = page.list.len() as i32;
+                let next = (count >= page_size).then_some(start + count);
+                Ok((page.list, next))
+            }
+        }))
+    }
+
     /// 搜索文件
     ///
     /// # 参数
@@ -65,7 +131,7 @@ impl BaiduPanClient {
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
         if let Some(dir) = dir {
-            params.insert("dir".to_string(), dir.to_string());
+            params.insert("dir".to_string(), dir.to_remote_path()?.into());
         }
         if let Some(recursion) = recursion {
             params.insert("recursion".to_string(), recursion.to_string());
@@ -98,4 +164,85 @@ impl BaiduPanClient {
         let response = self.get(&url, params).await?;
         BaiduPanClient::parse_response(response).await
     }
+
+    /// 根据路径获取文件信息
+    ///
+    /// 通过分页列出父目录查找目标文件，找不到时返回 errno 为 -9 的 API 错误。
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 文件或目录的完整路径
+    pub async fn file_info_by_path(&self, path: impl ToRemotePath) -> Result<FileInfo> {
+        let path = path.to_remote_path()?;
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return Err(Error::ParamError("不能获取根目录的文件信息".to_string()));
+        };
+
+        let mut entries = pin!(self.file_list_stream(&parent, &ListOptions::new()));
+        while let Some(info) = entries.try_next().await? {
+            if info.server_filename.as_deref() == Some(name) {
+                return Ok(info);
+            }
+        }
+
+        Err(Error::ApiError {
+            errno: -9,
+            message: format!("文件不存在: {}", path),
+        })
+    }
+}
+
+/// 收集流中的所有项，超过 `max_items` 项时停止请求并返回 [`Error::TooManyItems`]
+///
+/// 用于列出可能非常大的目录，避免一次性把海量文件读入内存。
+///
+/// # 示例
+/// ```no_run
+/// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+/// # use baidupan_sdk_rust::api::fileinfo::collect_all;
+/// # use baidupan_sdk_rust::models::file::ListOptions;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BaiduPanClient::new(Config::new("access_token"));
+///
+/// let options = ListOptions::new().with_recursive(true);
+/// let files = collect_all(client.file_list_all_stream("/apps/myapp", &options), 100_000).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn collect_all<T>(
+    stream: impl Stream<Item = Result<T>>,
+    max_items: usize,
+) -> Result<Vec<T>> {
+    let mut stream = pin!(stream);
+    let mut items = Vec::new();
+    while let Some(item) = stream.try_next().await? {
+        if items.len() >= max_items {
+            return Err(Error::TooManyItems { max_items });
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// 按页请求并展开为逐项的流
+///
+/// `fetch` 接收本页的起始位置，返回本页数据和下一页的起始位置(没有下一页时为 None)。
+/// 下一页的起始位置没有前进时同样结束，避免服务器返回异常游标时无限请求。
+pub(crate) fn paginate<F, Fut>(fetch: F) -> impl Stream<Item = Result<FileInfo>>
+where
+    F: FnMut(i32) -> Fut,
+    Fut: Future<Output = Result<(Vec<FileInfo>, Option<i32>)>>,
+{
+    stream::unfold((fetch, Some(0)), |(mut fetch, start)| async move {
+        let start = start?;
+        let (items, next): (Vec<Result<FileInfo>>, _) = match fetch(start).await {
+            Ok((list, next)) => (
+                list.into_iter().map(Ok).collect(),
+                next.filter(|&next| next > start),
+            ),
+            Err(e) => (vec![Err(e)], None),
+        };
+        Some((stream::iter(items), (fetch, next)))
+    })
+    .flatten()
 }
@@ -1,7 +1,66 @@
-use crate::models::file::FileOperationResponse;
-use crate::{BaiduPanClient, Result};
-use serde_json::json;
+use crate::api::fileupload::simulated_create;
+use crate::audit::AuditRecord;
+use crate::config::ClientMode;
+use crate::models::file::{
+    FileCreateResponse, FileMoveItem, FileOperationInfo, FileOperationResponse, OnConflict,
+    OnDuplicate, OperationMode, TaskPollOptions, TaskQueryResponse,
+};
+use crate::models::path::{RemotePath, ToRemotePath};
+use crate::{BaiduPanClient, Error, Result};
+use reqwest::Response;
+use serde_json::{Value, json};
 use std::collections::HashMap;
+use std::time::Instant;
+
+/// 文件或目录已存在的错误码
+const ERRNO_ALREADY_EXISTS: [i32; 2] = [-8, 31061];
+
+/// 文件或目录不存在的错误码
+const ERRNO_NOT_FOUND: [i32; 2] = [-9, 31066];
+
+/// 文件管理异步任务
+///
+/// 以 [`OperationMode::Async`] 或 [`OperationMode::Adaptive`] 执行文件管理操作时，
+/// 服务器返回 `taskid`，通过 [`BaiduPanClient::file_task`] 获取任务句柄后查询或等待结果。
+#[derive(Debug, Clone)]
+pub struct FileTask {
+    client: BaiduPanClient,
+    taskid: i64,
+}
+
+impl FileTask {
+    /// 任务 ID
+    pub fn taskid(&self) -> i64 {
+        self.taskid
+    }
+
+    /// 查询一次任务状态
+    pub async fn query(&self) -> Result<TaskQueryResponse> {
+        self.client.task_query(self.taskid).await
+    }
+
+    /// 轮询任务直到成功或失败
+    ///
+    /// 任务结束(无论成功或失败)时返回最后一次查询结果，可通过 `list` 查看每个文件的结果；
+    /// 超过 `options.timeout` 仍未结束时返回 [`Error::TaskTimeout`]。
+    pub async fn wait(&self, options: &TaskPollOptions) -> Result<TaskQueryResponse> {
+        let started = Instant::now();
+        loop {
+            let response = self.query().await?;
+            if response.status.is_finished() {
+                return Ok(response);
+            }
+            if let Some(timeout) = options.timeout
+                && started.elapsed() + options.interval > timeout
+            {
+                return Err(Error::TaskTimeout {
+                    taskid: self.taskid,
+                });
+            }
+            tokio::time::sleep(options.interval).await;
+        }
+    }
+}
 
 impl BaiduPanClient {
     /// 创建文件夹
@@ -9,7 +68,18 @@ impl BaiduPanClient {
     /// # 参数
     ///
     /// * `path` - 文件夹路径
-    pub async fn create_dir(&self, path: &str) -> Result<FileOperationResponse> {
+    /// * `on_conflict` - 路径已存在时的处理策略
+    ///
+    /// 服务器重命名文件夹时，可通过 [`FileCreateResponse::renamed_path`] 获取最终路径。
+    pub async fn create_dir(
+        &self,
+        path: impl ToRemotePath,
+        on_conflict: OnConflict,
+    ) -> Result<FileCreateResponse> {
+        let path = path.to_remote_path()?;
+        if self.check_mutation("mkdir", &[path.to_string()])? {
+            return Ok(simulated_create(path.as_str(), 0, true));
+        }
         let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
 
         let mut params = HashMap::new();
@@ -19,132 +89,414 @@ impl BaiduPanClient {
         let mut form_data = HashMap::new();
         form_data.insert("path".to_string(), path.to_string());
         form_data.insert("isdir".to_string(), "1".to_string());
-        form_data.insert("rtype".to_string(), "0".to_string());
+        form_data.insert("rtype".to_string(), on_conflict.rtype().to_string());
 
-        let response = self.post_form(&url, params, form_data).await?;
-        BaiduPanClient::parse_response(response).await
+        let result = async {
+            let response = self.post_form(&url, params, form_data).await?;
+            BaiduPanClient::parse_response::<FileCreateResponse>(response).await
+        }
+        .await;
+
+        let record = AuditRecord::new("mkdir", vec![path.into()])
+            .with_result(&result, |r| (vec![r.fs_id], r.request_id.clone()));
+        self.audit(record).await;
+        result
     }
 
-    /// 删除文件
+    /// 确保远程目录存在，类似 `mkdir -p`
+    ///
+    /// 先直接创建目标目录，服务器报告上级目录不存在时才逐级向上创建缺失的目录，
+    /// 因此只能访问应用目录的授权也可以使用，已存在的上级目录不会产生额外请求。
+    /// 目录已存在时视为成功；路径上存在同名文件时返回 [`Error::NotADirectory`]。
+    /// 已确认存在的目录会缓存在客户端中(克隆的客户端共享缓存)，重复调用不会再发送请求；
+    /// 通过本客户端删除、移动或重命名的目录会从缓存中移除。
     ///
     /// # 参数
     ///
-    /// * `paths` - 要删除的文件路径列表
-    pub async fn delete_files(&self, paths: &[&str]) -> Result<FileOperationResponse> {
-        let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
+    /// * `path` - 目录路径
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// client.ensure_dir("/apps/myapp/backup/2024/01").await?;
+    /// // 已缓存，不再发送请求
+    /// client.ensure_dir("/apps/myapp/backup/2024").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ensure_dir(&self, path: impl ToRemotePath) -> Result<()> {
+        let path = path.to_remote_path()?;
+        self.ensure_dir_within(path, &RemotePath::root()).await
+    }
 
-        let mut params = HashMap::new();
-        params.insert("method".to_string(), "filemanager".to_string());
-        params.insert("opera".to_string(), "delete".to_string());
-        params.insert("openapi".to_string(), "xpansdk".to_string());
+    /// 确保 `base` 下的目录存在，不会为 `base` 的上级目录发送创建请求
+    ///
+    /// 需要创建的上级目录超出 `base` 时返回服务器的错误。
+    pub(crate) async fn ensure_dir_within(
+        &self,
+        path: RemotePath,
+        base: &RemotePath,
+    ) -> Result<()> {
+        // 待创建的目录，栈顶为当前要创建的目录，下面依次是它的子目录
+        let mut pending = vec![path];
+        while let Some(dir) = pending.last() {
+            if dir.is_root() || self.is_known_dir(dir.as_str()) {
+                pending.pop();
+                continue;
+            }
 
-        let file_list: Vec<_> = paths.iter().map(|p| json!({"path": p})).collect();
+            match self.create_dir(dir, OnConflict::Fail).await {
+                Ok(_) => {}
+                Err(e) if e.errno().is_some_and(|n| ERRNO_ALREADY_EXISTS.contains(&n)) => {
+                    let info = self.file_info_by_path(dir).await?;
+                    if info.isdir != Some(1) {
+                        return Err(Error::NotADirectory {
+                            path: dir.to_string(),
+                        });
+                    }
+                }
+                Err(e) if e.errno().is_some_and(|n| ERRNO_NOT_FOUND.contains(&n)) => {
+                    // 上级目录不存在时先创建上级目录；上级目录已确认存在时不再重试
+                    match dir.parent() {
+                        Some(parent)
+                            if !parent.is_root()
+                                && parent.starts_with(base)
+                                && !self.is_known_dir(parent.as_str()) =>
+                        {
+                            pending.push(parent);
+                            continue;
+                        }
+                        _ => return Err(e),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
 
-        let mut form_data = HashMap::new();
-        form_data.insert("async".to_string(), "0".to_string());
-        form_data.insert("filelist".to_string(), serde_json::to_string(&file_list)?);
+            // 试运行时目录并未创建，不能记入缓存
+            if self.config.mode != ClientMode::DryRun
+                && let Ok(mut known_dirs) = self.known_dirs.lock()
+            {
+                known_dirs.insert(dir.to_string());
+            }
+            pending.pop();
+        }
 
-        let response = self.post_form(&url, params, form_data).await?;
-        BaiduPanClient::parse_response(response).await
+        Ok(())
+    }
+
+    /// 清空已确认存在的目录缓存
+    ///
+    /// 通过其他客户端或网页端删除目录后，调用本方法使 [`BaiduPanClient::ensure_dir`] 重新检查。
+    pub fn clear_dir_cache(&self) {
+        if let Ok(mut known_dirs) = self.known_dirs.lock() {
+            known_dirs.clear();
+        }
+    }
+
+    /// 删除文件
+    ///
+    /// 所有文件在一次请求中发送，大量文件请使用 [`BaiduPanClient::batch_delete`]。
+    ///
+    /// # 参数
+    ///
+    /// * `paths` - 要删除的文件路径列表
+    /// * `mode` - 执行方式，异步执行时通过返回的 `taskid` 查询结果
+    pub async fn delete_files(
+        &self,
+        paths: &[impl ToRemotePath],
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let file_list = paths
+            .iter()
+            .map(|p| Ok(json!({"path": p.to_remote_path()?.as_str()})))
+            .collect::<Result<Vec<_>>>()?;
+        self.file_manager("delete", &file_list, mode, None).await
     }
 
     /// 移动文件
     ///
+    /// 所有文件在一次请求中发送，大量文件请使用 [`BaiduPanClient::batch_move`]。
+    ///
+    /// 每个文件项指定源路径、目标目录和可选的新文件名，目标路径为 `dest/newname`。
+    /// 只知道完整目标路径时可以使用 [`FileMoveItem::to_path`] 构造。
+    ///
     /// # 参数
     ///
-    /// * `from_paths` - 源文件路径列表
-    /// * `to_paths` - 目标文件路径列表
+    /// * `items` - 要移动的文件项
+    /// * `on_duplicate` - 目标路径已存在时的处理策略
+    /// * `mode` - 执行方式，异步执行时通过返回的 `taskid` 查询结果
     pub async fn move_files(
         &self,
-        from_paths: &[&str],
-        to_paths: &[&str],
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        mode: OperationMode,
     ) -> Result<FileOperationResponse> {
-        if from_paths.len() != to_paths.len() {
-            return Err(crate::Error::ParamError(
-                "源路径和目标路径数量不匹配".to_string(),
-            ));
-        }
+        let file_list = move_file_list(items)?;
+        self.file_manager("move", &file_list, mode, Some(on_duplicate))
+            .await
+    }
+
+    /// 复制文件
+    ///
+    /// 所有文件在一次请求中发送，大量文件请使用 [`BaiduPanClient::batch_copy`]。
+    ///
+    /// 每个文件项指定源路径、目标目录和可选的新文件名，目标路径为 `dest/newname`。
+    /// 只知道完整目标路径时可以使用 [`FileMoveItem::to_path`] 构造。
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 要复制的文件项
+    /// * `on_duplicate` - 目标路径已存在时的处理策略
+    /// * `mode` - 执行方式，异步执行时通过返回的 `taskid` 查询结果
+    pub async fn copy_files(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let file_list = move_file_list(items)?;
+        self.file_manager("copy", &file_list, mode, Some(on_duplicate))
+            .await
+    }
 
+    /// 重命名文件
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 文件路径
+    /// * `new_name` - 新文件名，不能包含 `/`
+    /// * `mode` - 执行方式，异步执行时通过返回的 `taskid` 查询结果
+    pub async fn rename_file(
+        &self,
+        path: impl ToRemotePath,
+        new_name: &str,
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let path = path.to_remote_path()?;
+        let target = target_path(&path, new_name)?;
+        let file_list = vec![json!({"path": path.as_str(), "newname": target.file_name()})];
+        self.file_manager("rename", &file_list, mode, None).await
+    }
+
+    /// 查询文件管理异步任务的状态
+    ///
+    /// # 参数
+    ///
+    /// * `taskid` - 文件管理操作返回的任务 ID
+    pub async fn task_query(&self, taskid: i64) -> Result<TaskQueryResponse> {
         let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
 
         let mut params = HashMap::new();
-        params.insert("method".to_string(), "filemanager".to_string());
-        params.insert("opera".to_string(), "move".to_string());
+        params.insert("method".to_string(), "taskquery".to_string());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
-        let file_list: Vec<_> = from_paths
-            .iter()
-            .zip(to_paths.iter())
-            .map(|(from, to)| json!({"path": from, "dest": to, "newname": to}))
-            .collect();
-
         let mut form_data = HashMap::new();
-        form_data.insert("async".to_string(), "0".to_string());
-        form_data.insert("filelist".to_string(), serde_json::to_string(&file_list)?);
+        form_data.insert("taskid".to_string(), taskid.to_string());
 
         let response = self.post_form(&url, params, form_data).await?;
         BaiduPanClient::parse_response(response).await
     }
 
-    /// 复制文件
+    /// 获取异步任务句柄
     ///
     /// # 参数
     ///
-    /// * `from_paths` - 源文件路径列表
-    /// * `to_paths` - 目标文件路径列表
-    pub async fn copy_files(
+    /// * `taskid` - 文件管理操作返回的任务 ID
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::file::{OperationMode, TaskPollOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let response = client
+    ///     .delete_files(&["/apps/myapp/old"], OperationMode::Async)
+    ///     .await?;
+    /// if let Some(taskid) = response.taskid {
+    ///     let result = client.file_task(taskid).wait(&TaskPollOptions::new()).await?;
+    ///     println!("任务状态: {:?}", result.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn file_task(&self, taskid: i64) -> FileTask {
+        FileTask {
+            client: self.clone(),
+            taskid,
+        }
+    }
+
+    /// 目录是否已确认存在
+    fn is_known_dir(&self, path: &str) -> bool {
+        self.known_dirs
+            .lock()
+            .is_ok_and(|known_dirs| known_dirs.contains(path))
+    }
+
+    /// 从目录缓存中移除 `path` 及其子目录
+    fn forget_dirs_under(&self, path: &str) {
+        let path = path.trim_end_matches('/');
+        if let Ok(mut known_dirs) = self.known_dirs.lock() {
+            known_dirs.retain(|dir| {
+                let under = dir
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+                !under
+            });
+        }
+    }
+
+    /// 发送文件管理请求并解析响应
+    async fn file_manager(
         &self,
-        from_paths: &[&str],
-        to_paths: &[&str],
+        opera: &str,
+        file_list: &[Value],
+        mode: OperationMode,
+        on_duplicate: Option<OnDuplicate>,
     ) -> Result<FileOperationResponse> {
-        if from_paths.len() != to_paths.len() {
-            return Err(crate::Error::ParamError(
-                "源路径和目标路径数量不匹配".to_string(),
-            ));
+        let targets: Vec<String> = file_list.iter().map(describe_entry).collect();
+        if self.check_mutation(opera, &targets)? {
+            return Ok(FileOperationResponse {
+                taskid: None,
+                info: Some(file_list.iter().map(simulated_info).collect()),
+                request_id: None,
+            });
+        }
+
+        let result = async {
+            let response = self
+                .file_manager_request(opera, file_list, mode, on_duplicate)
+                .await?;
+            BaiduPanClient::parse_response::<FileOperationResponse>(response).await
         }
+        .await;
+
+        let record = entries_record(opera, file_list).with_result(&result, |r| {
+            let info = r.info.as_deref().unwrap_or_default();
+            (info_fs_ids(info), r.request_id.clone())
+        });
+        self.audit(record).await;
+        result
+    }
 
+    /// 发送文件管理请求，返回原始响应
+    pub(crate) async fn file_manager_request(
+        &self,
+        opera: &str,
+        file_list: &[Value],
+        mode: OperationMode,
+        on_duplicate: Option<OnDuplicate>,
+    ) -> Result<Response> {
         let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "filemanager".to_string());
-        params.insert("opera".to_string(), "copy".to_string());
+        params.insert("opera".to_string(), opera.to_string());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
-        let file_list: Vec<_> = from_paths
-            .iter()
-            .zip(to_paths.iter())
-            .map(|(from, to)| json!({"path": from, "dest": to, "newname": to}))
-            .collect();
+        // 删除、移动或重命名后目录可能不再存在
+        if opera != "copy" {
+            for path in file_list.iter().filter_map(|item| item["path"].as_str()) {
+                self.forget_dirs_under(path);
+            }
+        }
 
         let mut form_data = HashMap::new();
-        form_data.insert("async".to_string(), "0".to_string());
-        form_data.insert("filelist".to_string(), serde_json::to_string(&file_list)?);
+        form_data.insert("async".to_string(), mode.async_value().to_string());
+        form_data.insert("filelist".to_string(), serde_json::to_string(file_list)?);
+        if let Some(on_duplicate) = on_duplicate {
+            form_data.insert("ondup".to_string(), on_duplicate.ondup().to_string());
+        }
 
-        let response = self.post_form(&url, params, form_data).await?;
-        BaiduPanClient::parse_response(response).await
+        self.post_form(&url, params, form_data).await
     }
+}
 
-    /// 重命名文件
-    ///
-    /// # 参数
-    ///
-    /// * `path` - 文件路径
-    /// * `new_name` - 新文件名
-    pub async fn rename_file(&self, path: &str, new_name: &str) -> Result<FileOperationResponse> {
-        let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
+/// 构建移动/复制请求的 filelist
+fn move_file_list(items: &[FileMoveItem]) -> Result<Vec<Value>> {
+    items.iter().map(move_file_entry).collect()
+}
 
-        let mut params = HashMap::new();
-        params.insert("method".to_string(), "filemanager".to_string());
-        params.insert("opera".to_string(), "rename".to_string());
-        params.insert("openapi".to_string(), "xpansdk".to_string());
+/// 构建移动/复制请求中的单个文件项
+pub(crate) fn move_file_entry(item: &FileMoveItem) -> Result<Value> {
+    let path = RemotePath::new(&item.path)?;
+    let dest = RemotePath::new(&item.dest)?;
+    let target = target_path(&dest, item.target_name())?;
+    Ok(json!({
+        "path": path.as_str(),
+        "dest": dest.as_str(),
+        "newname": target.file_name(),
+    }))
+}
 
-        let file_list = vec![json!({"path": path, "newname": new_name})];
+/// 文件项的目标路径，删除操作为 None
+fn entry_target(entry: &Value) -> Option<String> {
+    let newname = entry["newname"].as_str()?;
+    let dir = match entry["dest"].as_str() {
+        Some(dest) => dest.to_string(),
+        None => {
+            let path = entry["path"].as_str()?;
+            path[..path.rfind('/')?].to_string()
+        }
+    };
+    Some(format!("{}/{}", dir.trim_end_matches('/'), newname))
+}
 
-        let mut form_data = HashMap::new();
-        form_data.insert("async".to_string(), "0".to_string());
-        form_data.insert("filelist".to_string(), serde_json::to_string(&file_list)?);
+/// 由文件项构建审计记录
+pub(crate) fn entries_record(opera: &str, file_list: &[Value]) -> AuditRecord {
+    let paths = file_list
+        .iter()
+        .filter_map(|entry| entry["path"].as_str().map(str::to_string))
+        .collect();
+    let destinations = file_list.iter().filter_map(entry_target).collect();
+    AuditRecord::new(opera, paths).with_destinations(destinations)
+}
 
-        let response = self.post_form(&url, params, form_data).await?;
-        BaiduPanClient::parse_response(response).await
+/// 文件操作结果中的文件 ID
+pub(crate) fn info_fs_ids(info: &[FileOperationInfo]) -> Vec<i64> {
+    info.iter().filter_map(|i| i.fs_id).collect()
+}
+
+/// 描述文件项，用于试运行输出
+pub(crate) fn describe_entry(entry: &Value) -> String {
+    let path = entry["path"].as_str().unwrap_or_default();
+    match entry_target(entry) {
+        Some(target) => format!("{} -> {}", path, target),
+        None => path.to_string(),
+    }
+}
+
+/// 试运行模式下模拟的单个文件操作结果
+fn simulated_info(entry: &Value) -> FileOperationInfo {
+    let path = entry["path"].as_str().map(str::to_string);
+    let to = entry_target(entry);
+    FileOperationInfo {
+        from: to.as_ref().and(path.clone()),
+        path,
+        fs_id: None,
+        errno: Some(0),
+        to,
+    }
+}
+
+/// 校验新文件名并返回 `dir` 下的目标路径
+fn target_path(dir: &RemotePath, name: &str) -> Result<RemotePath> {
+    if name.contains('/') {
+        return Err(Error::InvalidPath {
+            path: name.to_string(),
+            reason: "文件名不能包含 /".to_string(),
+        });
+    }
+    let target = dir.join(name)?;
+    if target == *dir {
+        return Err(Error::InvalidPath {
+            path: name.to_string(),
+            reason: "文件名不能为空".to_string(),
+        });
     }
+    Ok(target)
 }
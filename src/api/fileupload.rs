@@ -1,11 +1,38 @@
-use crate::models::file::{FileCreateResponse, FilePrecreateResponse};
-use crate::{BaiduPanClient, Result};
-use serde_json::json;
+use crate::audit::AuditRecord;
+use crate::models::file::{
+    FileCreateResponse, FilePrecreateResponse, LocalTimes, LocateUploadResponse, OnConflict,
+    SliceUploadResponse,
+};
+use crate::models::path::ToRemotePath;
+use crate::models::upload::{UploadEvent, UploadOptions};
+use crate::models::user::VipTier;
+use crate::{BaiduPanClient, Error, Result};
+use bytes::Bytes;
+use futures_util::{StreamExt, stream};
+use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
+/// 分片发送时回报进度的粒度
+const PROGRESS_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 上传服务器被标记为不可用后的冷却时间
+const UNHEALTHY_HOST_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+/// 试运行模式下预创建返回的上传 ID
+const DRY_RUN_UPLOADID: &str = "dry-run";
+
+/// 单次上传会话使用的上传服务器列表
+struct UploadHosts {
+    /// 按优先级排列的服务器地址
+    hosts: Vec<String>,
+    /// 当前使用的服务器下标
+    current: usize,
+}
+
 impl BaiduPanClient {
     /// 预创建文件
     ///
@@ -14,29 +41,59 @@ impl BaiduPanClient {
     /// * `path` - 上传路径
     /// * `size` - 文件大小
     /// * `block_list` - MD5 列表
+    /// * `on_conflict` - 路径冲突时的处理策略
+    /// * `local_times` - 文件在客户端的创建/修改时间，None 表示不发送
     pub async fn file_precreate(
         &self,
-        path: &str,
+        path: impl ToRemotePath,
         size: i64,
         block_list: &[String],
+        on_conflict: OnConflict,
+        local_times: Option<LocalTimes>,
     ) -> Result<FilePrecreateResponse> {
+        let path = path.to_remote_path()?;
+        if self.check_mutation("precreate", &[path.to_string()])? {
+            return Ok(FilePrecreateResponse {
+                uploadid: DRY_RUN_UPLOADID.to_string(),
+                return_type: 0,
+                block_list: Some((0..block_list.len() as i32).collect()),
+                request_id: None,
+                file_info: None,
+            });
+        }
         let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "precreate".to_string());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
-        let body = json!({
-            "path": path,
+        let mut body = json!({
+            "path": path.as_str(),
             "size": size,
             "isdir": 0,
             "autoinit": 1,
-            "rtype": 1,
+            "rtype": on_conflict.rtype(),
             "block_list": serde_json::to_string(block_list)?
         });
+        apply_local_times(&mut body, local_times);
 
-        let response = self.post(&url, params, Some(body)).await?;
-        BaiduPanClient::parse_response(response).await
+        let result = async {
+            let response = self.post(&url, params, Some(body)).await?;
+            BaiduPanClient::parse_response::<FilePrecreateResponse>(response).await
+        }
+        .await;
+
+        // 秒传成功时文件已经创建
+        let operation = match &result {
+            Ok(r) if r.return_type == 1 => "rapid_upload",
+            _ => "precreate",
+        };
+        let record = AuditRecord::new(operation, vec![path.into()]).with_result(&result, |r| {
+            let fs_id = r.file_info.as_ref().and_then(|info| info.fs_id);
+            (fs_id.into_iter().collect(), r.request_id.clone())
+        });
+        self.audit(record).await;
+        result
     }
 
     /// 创建文件
@@ -47,30 +104,88 @@ impl BaiduPanClient {
     /// * `size` - 文件大小
     /// * `uploadid` - 上传 ID
     /// * `block_list` - MD5 列表
+    /// * `on_conflict` - 路径冲突时的处理策略
+    /// * `local_times` - 文件在客户端的创建/修改时间，None 表示不发送
+    ///
+    /// 服务器重命名文件时，可通过 [`FileCreateResponse::renamed_path`] 获取最终路径。
     pub async fn file_create(
         &self,
-        path: &str,
+        path: impl ToRemotePath,
         size: i64,
         uploadid: &str,
         block_list: &[String],
+        on_conflict: OnConflict,
+        local_times: Option<LocalTimes>,
     ) -> Result<FileCreateResponse> {
+        let path = path.to_remote_path()?;
+        if self.check_mutation("create", &[path.to_string()])? {
+            return Ok(simulated_create(path.as_str(), size, false));
+        }
         let url = format!("{}/rest/2.0/xpan/file", self.config.server.pan_url);
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "create".to_string());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
-        let body = json!({
-            "path": path,
+        let mut body = json!({
+            "path": path.as_str(),
             "size": size,
             "isdir": 0,
             "uploadid": uploadid,
-            "rtype": 1,
+            "rtype": on_conflict.rtype(),
             "block_list": serde_json::to_string(block_list)?
         });
+        apply_local_times(&mut body, local_times);
 
-        let response = self.post(&url, params, Some(body)).await?;
-        BaiduPanClient::parse_response(response).await
+        let result = async {
+            let response = self.post(&url, params, Some(body)).await?;
+            BaiduPanClient::parse_response::<FileCreateResponse>(response).await
+        }
+        .await;
+
+        let record = AuditRecord::new("create", vec![path.into()])
+            .with_result(&result, |r| (vec![r.fs_id], r.request_id.clone()));
+        self.audit(record).await;
+        result
+    }
+
+    /// 查询上传服务器
+    ///
+    /// 返回本次上传(uploadid)推荐使用的上传服务器列表，
+    /// 可通过 [`LocateUploadResponse::upload_hosts`] 获取按优先级排列的地址。
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 上传路径
+    /// * `uploadid` - 上传 ID
+    pub async fn locate_upload(
+        &self,
+        path: impl ToRemotePath,
+        uploadid: &str,
+    ) -> Result<LocateUploadResponse> {
+        let url = format!("{}/rest/2.0/pcs/file", self.config.server.pcs_url);
+
+        let mut params = HashMap::new();
+        params.insert("method".to_string(), "locateupload".to_string());
+        params.insert("appid".to_string(), "250528".to_string());
+        params.insert("path".to_string(), path.to_remote_path()?.into());
+        params.insert("uploadid".to_string(), uploadid.to_string());
+        params.insert("upload_version".to_string(), "2.0".to_string());
+        params.insert("openapi".to_string(), "xpansdk".to_string());
+
+        let response = self.get(&url, params).await?;
+        let locate: LocateUploadResponse = BaiduPanClient::parse_response(response).await?;
+
+        if locate.error_code != 0 {
+            return Err(Error::ApiError {
+                errno: locate.error_code,
+                message: locate
+                    .error_msg
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            });
+        }
+
+        Ok(locate)
     }
 
     /// 分片上传
@@ -81,14 +196,73 @@ impl BaiduPanClient {
     /// * `uploadid` - 上传 ID
     /// * `partseq` - 分片序号(从 0 开始)
     /// * `data` - 分片数据
+    ///
+    /// 返回值中的 `md5` 为服务器计算的分片 MD5，可用于校验分片是否完整到达。
     pub async fn upload_slice(
         &self,
-        path: &str,
+        path: impl ToRemotePath,
         uploadid: &str,
         partseq: i32,
         data: &[u8],
-    ) -> Result<String> {
-        let url = format!("{}/rest/2.0/pcs/superfile2", self.config.server.pcs_url);
+    ) -> Result<SliceUploadResponse> {
+        let path = path.to_remote_path()?;
+        let target = format!("{} 分片 {}", path, partseq);
+        if self.check_mutation("upload", &[target])? {
+            return Ok(SliceUploadResponse {
+                md5: format!("{:x}", md5::compute(data)),
+                partseq: Some(partseq as i64),
+                request_id: None,
+            });
+        }
+        let part = reqwest::multipart::Part::bytes(data.to_vec());
+        self.send_slice(
+            &self.config.server.pcs_url,
+            path.as_str(),
+            uploadid,
+            partseq,
+            part,
+        )
+        .await
+    }
+
+    /// 分片上传，并在发送过程中回报已发送的字节数
+    async fn upload_slice_with_progress(
+        &self,
+        host: &str,
+        path: &str,
+        uploadid: &str,
+        partseq: i32,
+        data: Bytes,
+        on_sent: impl Fn(u64) + Send + Sync + 'static,
+    ) -> Result<SliceUploadResponse> {
+        let len = data.len() as u64;
+        let chunks: Vec<Bytes> = (0..data.len())
+            .step_by(PROGRESS_CHUNK_SIZE)
+            .map(|start| data.slice(start..(start + PROGRESS_CHUNK_SIZE).min(data.len())))
+            .collect();
+
+        let mut sent = 0u64;
+        let stream = stream::iter(chunks).map(move |chunk| {
+            sent += chunk.len() as u64;
+            on_sent(sent);
+            Ok::<_, std::io::Error>(chunk)
+        });
+
+        let part =
+            reqwest::multipart::Part::stream_with_length(reqwest::Body::wrap_stream(stream), len);
+        self.send_slice(host, path, uploadid, partseq, part).await
+    }
+
+    /// 发送 superfile2 分片请求
+    async fn send_slice(
+        &self,
+        host: &str,
+        path: &str,
+        uploadid: &str,
+        partseq: i32,
+        part: reqwest::multipart::Part,
+    ) -> Result<SliceUploadResponse> {
+        let url = format!("{}/rest/2.0/pcs/superfile2", host);
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "upload".to_string());
@@ -99,8 +273,7 @@ impl BaiduPanClient {
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
         // 构建 multipart 表单
-        let form = reqwest::multipart::Form::new()
-            .part("file", reqwest::multipart::Part::bytes(data.to_vec()));
+        let form = reqwest::multipart::Form::new().part("file", part);
 
         let mut url = url::Url::parse(&url)?;
         for (key, value) in params {
@@ -120,26 +293,160 @@ impl BaiduPanClient {
     ///
     /// * `local_path` - 本地文件路径
     /// * `remote_path` - 远程文件路径
-    /// * `chunk_size` - 分片大小(默认 4MB)
+    /// * `chunk_size` - 分片大小(默认按会员等级选择：普通用户 4MB，会员 16MB，超级会员 32MB)
     pub async fn upload_file(
         &self,
         local_path: impl AsRef<Path>,
-        remote_path: &str,
+        remote_path: impl ToRemotePath,
         chunk_size: Option<usize>,
     ) -> Result<FileCreateResponse> {
-        let chunk_size = chunk_size.unwrap_or(4 * 1024 * 1024); // 4MB
+        let options = UploadOptions {
+            chunk_size,
+            ..UploadOptions::default()
+        };
+        self.upload_file_with_options(local_path, remote_path, &options)
+            .await
+    }
+
+    /// 上传文件，支持进度回调和分片重试
+    ///
+    /// 上传前会读取账号会员等级(结果会缓存)，据此选择分片大小，并检查单文件大小和分片数量上限，
+    /// 超限时返回 [`Error::FileTooLarge`] 或 [`Error::TooManySlices`]。
+    ///
+    /// 默认会把本地文件的创建/修改时间作为 `local_ctime` / `local_mtime` 发送，
+    /// 网盘中显示的时间与本地保持一致。
+    ///
+    /// 路径冲突时按 `options.on_conflict` 处理，服务器重命名文件时可通过
+    /// [`FileCreateResponse::renamed_path`] 获取最终路径。
+    ///
+    /// 分片上传前会通过 [`BaiduPanClient::locate_upload`] 查询上传服务器，
+    /// 分片遇到连接错误或 5xx 时切换到下一个服务器，并在客户端内记录不可用的服务器。
+    ///
+    /// 每个分片上传后会用服务器返回的 MD5 与本地计算的分片 MD5 比对，
+    /// 不一致时按重试次数重新上传，仍失败则返回 [`Error::SliceMd5Mismatch`]。
+    ///
+    /// 上传过程中会依次触发 [`UploadEvent`] 事件：计算 MD5、预创建(含是否秒传)、
+    /// 每个分片的开始/发送进度/重试/完成，以及最终的文件创建。
+    ///
+    /// # 参数
+    ///
+    /// * `local_path` - 本地文件路径
+    /// * `remote_path` - 远程文件路径
+    /// * `options` - 上传选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::upload::{UploadEvent, UploadOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let options = UploadOptions::new().with_progress(|event| {
+    ///     if let UploadEvent::SliceProgress { total_sent, total_bytes, .. } = event {
+    ///         println!("已上传 {}/{} 字节", total_sent, total_bytes);
+    ///     }
+    /// });
+    /// client.upload_file_with_options("big.iso", "/apps/myapp/big.iso", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_file_with_options(
+        &self,
+        local_path: impl AsRef<Path>,
+        remote_path: impl ToRemotePath,
+        options: &UploadOptions,
+    ) -> Result<FileCreateResponse> {
+        let remote_path = remote_path.to_remote_path()?;
+        let (response, _) = self
+            .upload_file_inner(local_path.as_ref(), remote_path.as_str(), options)
+            .await?;
+        Ok(response)
+    }
+
+    /// 上传文件，额外返回是否秒传成功
+    pub(crate) async fn upload_file_inner(
+        &self,
+        local_path: &Path,
+        remote_path: &str,
+        options: &UploadOptions,
+    ) -> Result<(FileCreateResponse, bool)> {
+        let target = format!("{} -> {}", local_path.display(), remote_path);
+        if self.check_mutation("upload", &[target])? {
+            let size = tokio::fs::metadata(local_path).await?.len() as i64;
+            return Ok((simulated_create(remote_path, size, false), false));
+        }
+
+        // 按会员等级确定分片大小
+        // 获取用户信息失败时按普通用户处理，4MB 分片对所有等级都有效
+        let tier = match self.vip_tier().await {
+            Ok(tier) => tier,
+            Err(e) => {
+                if self.config.debug {
+                    eprintln!("获取会员等级失败，按普通用户处理: {}", e);
+                }
+                VipTier::Normal
+            }
+        };
+        let chunk_size = match options.chunk_size {
+            Some(0) => return Err(Error::ParamError("分片大小必须大于 0".to_string())),
+            Some(size) if tier == VipTier::Normal && size != tier.max_slice_size() => {
+                return Err(Error::ParamError(format!(
+                    "{}的分片大小固定为 {} 字节，不支持 {} 字节",
+                    tier,
+                    tier.max_slice_size(),
+                    size
+                )));
+            }
+            Some(size) if size > tier.max_slice_size() => {
+                return Err(Error::ParamError(format!(
+                    "分片大小 {} 字节超出{}上限 {} 字节",
+                    size,
+                    tier,
+                    tier.max_slice_size()
+                )));
+            }
+            Some(size) => size,
+            None => tier.max_slice_size(),
+        };
 
         // 读取文件
-        let mut file = File::open(local_path.as_ref()).await?;
-        let file_size = file.metadata().await?.len() as i64;
+        let mut file = File::open(local_path).await?;
+        let metadata = file.metadata().await?;
+        let file_size = metadata.len() as i64;
+        let total_bytes = file_size as u64;
+
+        // 本地时间戳：优先使用显式指定的值，否则按需从文件元数据读取
+        let local_times = options.local_times.or_else(|| {
+            options
+                .preserve_times
+                .then(|| LocalTimes::from_metadata(&metadata))
+        });
+
+        // 检查单文件大小和分片数量上限
+        if total_bytes > tier.max_file_size() {
+            return Err(Error::FileTooLarge {
+                size: total_bytes,
+                max_size: tier.max_file_size(),
+                tier,
+            });
+        }
+        let slice_count = total_bytes.div_ceil(chunk_size as u64);
+        if slice_count > VipTier::MAX_SLICE_COUNT {
+            return Err(Error::TooManySlices {
+                count: slice_count,
+                max: VipTier::MAX_SLICE_COUNT,
+            });
+        }
 
         // 计算分片 MD5
+        options.emit(UploadEvent::HashStarted { total_bytes });
+
         let mut block_list = Vec::new();
         let mut buffer = vec![0u8; chunk_size];
         let mut offset = 0;
 
         while offset < file_size as usize {
-            let n = file.read(&mut buffer).await?;
+            let n = read_full(&mut file, &mut buffer).await?;
             if n == 0 {
                 break;
             }
@@ -147,57 +454,304 @@ impl BaiduPanClient {
             let md5 = format!("{:x}", md5::compute(&buffer[..n]));
             block_list.push(md5);
             offset += n;
+
+            options.emit(UploadEvent::Hashing {
+                bytes_hashed: offset as u64,
+                total_bytes,
+            });
         }
 
+        options.emit(UploadEvent::HashCompleted {
+            block_count: block_list.len(),
+        });
+
         // 预创建
         let precreate_resp = self
-            .file_precreate(remote_path, file_size, &block_list)
+            .file_precreate(
+                remote_path,
+                file_size,
+                &block_list,
+                options.on_conflict,
+                local_times,
+            )
             .await?;
 
+        let rapid_upload = precreate_resp.return_type == 1;
+        options.emit(UploadEvent::Precreated {
+            uploadid: precreate_resp.uploadid.clone(),
+            rapid_upload,
+        });
+
         // 如果秒传成功
-        if precreate_resp.return_type == 1 {
-            if let Some(file_info) = precreate_resp.file_info {
-                return Ok(FileCreateResponse {
-                    fs_id: file_info.fs_id.unwrap_or(0),
-                    path: file_info.path.unwrap_or_default(),
-                    server_filename: file_info.server_filename.unwrap_or_default(),
-                    size: file_info.size.unwrap_or(0),
-                    md5: file_info.md5,
-                    ctime: file_info.ctime.unwrap_or(0),
-                    mtime: file_info.mtime.unwrap_or(0),
-                    isdir: file_info.isdir.unwrap_or(0),
-                    category: file_info.category.unwrap_or(0),
-                });
-            }
+        if rapid_upload && let Some(file_info) = precreate_resp.file_info {
+            let response = FileCreateResponse {
+                fs_id: file_info.fs_id.unwrap_or(0),
+                path: file_info.path.unwrap_or_default(),
+                server_filename: file_info.server_filename.unwrap_or_default(),
+                size: file_info.size.unwrap_or(0),
+                md5: file_info.md5,
+                ctime: file_info.ctime.unwrap_or(0),
+                mtime: file_info.mtime.unwrap_or(0),
+                isdir: file_info.isdir.unwrap_or(0),
+                category: file_info.category.unwrap_or(0),
+                request_id: precreate_resp.request_id,
+            };
+            options.emit(UploadEvent::Created {
+                fs_id: response.fs_id,
+                path: response.path.clone(),
+            });
+            return Ok((response, true));
         }
 
+        // 查询上传服务器，失败时使用默认服务器
+        let mut hosts = self
+            .upload_hosts(remote_path, &precreate_resp.uploadid)
+            .await;
+
         // 重新打开文件进行分片上传
-        let mut file = File::open(local_path.as_ref()).await?;
-        let mut offset = 0;
+        let mut file = File::open(local_path).await?;
+        let mut offset = 0u64;
         let mut partseq = 0;
 
-        while offset < file_size as usize {
+        while offset < total_bytes {
             let mut buffer = vec![0u8; chunk_size];
-            let n = file.read(&mut buffer).await?;
+            let n = read_full(&mut file, &mut buffer).await?;
             if n == 0 {
                 break;
             }
 
             buffer.truncate(n);
-            self.upload_slice(remote_path, &precreate_resp.uploadid, partseq, &buffer)
-                .await?;
+            let data = Bytes::from(buffer);
+            let slice_size = n as u64;
 
-            offset += n;
+            options.emit(UploadEvent::SliceStarted {
+                partseq,
+                offset,
+                size: slice_size,
+            });
+
+            let mut attempt = 0;
+            loop {
+                let progress = options.clone();
+                let host = hosts.select(self).to_string();
+                let result = self
+                    .upload_slice_with_progress(
+                        &host,
+                        remote_path,
+                        &precreate_resp.uploadid,
+                        partseq,
+                        data.clone(),
+                        move |bytes_sent| {
+                            progress.emit(UploadEvent::SliceProgress {
+                                partseq,
+                                bytes_sent,
+                                slice_size,
+                                total_sent: offset + bytes_sent,
+                                total_bytes,
+                            });
+                        },
+                    )
+                    .await;
+
+                // 服务器返回的分片 MD5 与本地不一致时视为传输损坏，重新上传
+                let result = result.and_then(|resp| {
+                    if resp.md5.eq_ignore_ascii_case(&block_list[partseq as usize]) {
+                        Ok(resp)
+                    } else {
+                        Err(Error::SliceMd5Mismatch {
+                            partseq,
+                            expected: block_list[partseq as usize].clone(),
+                            actual: resp.md5,
+                        })
+                    }
+                });
+
+                // 连接失败或服务器 5xx 时标记该服务器不可用，切换到下一个
+                if let Err(e) = &result
+                    && is_host_failure(e)
+                {
+                    self.mark_host_unhealthy(&host);
+                    hosts.rotate();
+                }
+
+                match result {
+                    Ok(_) => break,
+                    Err(e) if attempt < options.max_retries => {
+                        attempt += 1;
+                        options.emit(UploadEvent::SliceRetry {
+                            partseq,
+                            attempt,
+                            reason: e.to_string(),
+                        });
+                        tokio::time::sleep(options.backoff(attempt)).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
+            options.emit(UploadEvent::SliceCompleted {
+                partseq,
+                size: slice_size,
+            });
+
+            offset += slice_size;
             partseq += 1;
         }
 
         // 创建文件
-        self.file_create(
-            remote_path,
-            file_size,
-            &precreate_resp.uploadid,
-            &block_list,
-        )
-        .await
+        let response = self
+            .file_create(
+                remote_path,
+                file_size,
+                &precreate_resp.uploadid,
+                &block_list,
+                options.on_conflict,
+                local_times,
+            )
+            .await?;
+
+        options.emit(UploadEvent::Created {
+            fs_id: response.fs_id,
+            path: response.path.clone(),
+        });
+
+        Ok((response, false))
+    }
+}
+
+impl BaiduPanClient {
+    /// 获取本次上传使用的服务器列表，默认服务器始终作为最后的备选
+    async fn upload_hosts(&self, path: &str, uploadid: &str) -> UploadHosts {
+        let mut hosts = match self.locate_upload(path, uploadid).await {
+            Ok(locate) => locate.upload_hosts(),
+            Err(e) => {
+                if self.config.debug {
+                    eprintln!("locateupload 失败，使用默认上传服务器: {}", e);
+                }
+                Vec::new()
+            }
+        };
+
+        let default_host = self.config.server.pcs_url.trim_end_matches('/').to_string();
+        if !hosts.contains(&default_host) {
+            hosts.push(default_host);
+        }
+
+        UploadHosts { hosts, current: 0 }
+    }
+
+    /// 标记上传服务器不可用
+    fn mark_host_unhealthy(&self, host: &str) {
+        if self.config.debug {
+            eprintln!("上传服务器不可用: {}", host);
+        }
+        if let Ok(mut unhealthy) = self.unhealthy_hosts.lock() {
+            unhealthy.insert(host.to_string(), Instant::now());
+        }
+    }
+
+    /// 上传服务器是否可用(不可用标记超过冷却时间后恢复)
+    fn is_host_healthy(&self, host: &str) -> bool {
+        match self.unhealthy_hosts.lock() {
+            Ok(unhealthy) => unhealthy
+                .get(host)
+                .is_none_or(|marked| marked.elapsed() >= UNHEALTHY_HOST_COOLDOWN),
+            Err(_) => true,
+        }
+    }
+}
+
+impl UploadHosts {
+    /// 从当前位置开始选择第一个可用的服务器，全部不可用时使用当前服务器
+    fn select(&mut self, client: &BaiduPanClient) -> &str {
+        let len = self.hosts.len();
+        if let Some(index) = (0..len)
+            .map(|i| (self.current + i) % len)
+            .find(|&i| client.is_host_healthy(&self.hosts[i]))
+        {
+            self.current = index;
+        }
+        &self.hosts[self.current]
+    }
+
+    /// 切换到下一个服务器
+    fn rotate(&mut self) {
+        self.current = (self.current + 1) % self.hosts.len();
+    }
+}
+
+/// 将本地时间戳写入请求体
+fn apply_local_times(body: &mut Value, local_times: Option<LocalTimes>) {
+    let Some(times) = local_times else {
+        return;
+    };
+    if let Some(ctime) = times.ctime {
+        body["local_ctime"] = json!(ctime);
+    }
+    if let Some(mtime) = times.mtime {
+        body["local_mtime"] = json!(mtime);
+    }
+}
+
+/// 是否为需要切换上传服务器的错误(连接失败、超时或服务器 5xx)
+fn is_host_failure(error: &Error) -> bool {
+    match error {
+        Error::HttpError(e) => {
+            e.is_connect()
+                || e.is_timeout()
+                || e.status().is_some_and(|status| status.is_server_error())
+        }
+        Error::ApiError { errno, .. } => (500..600).contains(errno),
+        _ => false,
+    }
+}
+
+/// 读满缓冲区，直到文件结束
+///
+/// 分片的 MD5 必须按完整分片计算，单次 `read` 可能只返回部分数据。
+async fn read_full(file: &mut File, buffer: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        let n = file.read(&mut buffer[filled..]).await?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(filled)
+}
+
+/// 计算本地文件的完整 MD5
+pub(crate) async fn file_md5(path: &Path) -> Result<String> {
+    let mut file = File::open(path).await?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            break;
+        }
+        context.consume(&buffer[..n]);
+    }
+    Ok(format!("{:x}", context.finalize()))
+}
+
+/// 试运行模式下模拟的文件/目录创建结果
+pub(crate) fn simulated_create(path: &str, size: i64, isdir: bool) -> FileCreateResponse {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    FileCreateResponse {
+        fs_id: 0,
+        path: path.to_string(),
+        server_filename: path.rsplit('/').next().unwrap_or_default().to_string(),
+        size,
+        md5: None,
+        ctime: now,
+        mtime: now,
+        isdir: isdir as i32,
+        category: 0,
+        request_id: None,
     }
 }
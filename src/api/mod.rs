@@ -1,6 +1,15 @@
 /// 认证 API
 pub mod auth;
 
+/// 批量文件操作 API
+pub mod batch;
+
+/// 目录下载 API
+pub mod dirdownload;
+
+/// 目录上传 API
+pub mod dirupload;
+
 /// 文件下载 API
 pub mod filedownload;
 
@@ -16,5 +25,11 @@ pub mod fileupload;
 /// 多媒体文件 API
 pub mod multimedia;
 
+/// 远程文件随机读取 API
+pub mod remotefile;
+
+/// 限定根目录的客户端
+pub mod scoped;
+
 /// 用户信息 API
 pub mod userinfo;
@@ -1,7 +1,20 @@
+use crate::api::fileinfo::paginate;
 use crate::client::BaiduPanClient;
-use crate::error::Result;
-use crate::models::file::{FileListResponse, FileMetasResponse};
+use crate::error::{Error, Result};
+use crate::models::file::{
+    FileInfo, FileListResponse, FileMetaInfo, FileMetasResponse, ListOptions,
+};
+use crate::models::path::{RemotePath, ToRemotePath};
+use futures_util::future::Either;
+use futures_util::{Stream, TryStreamExt, stream};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// filemetas 接口单次最多查询的文件数量
+const FILE_METAS_BATCH_SIZE: usize = 100;
+
+/// dlink 缓存有效期(dlink 有效期为 8 小时，预留 10 分钟余量)
+const DLINK_TTL: Duration = Duration::from_secs(8 * 60 * 60 - 10 * 60);
 
 impl BaiduPanClient {
     /// 递归获取所有文件列表
@@ -14,9 +27,9 @@ impl BaiduPanClient {
     ///
     /// # 示例
     /// ```no_run
-    /// # use baidupan_sdk_rust::BaiduPanClient;
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = BaiduPanClient::new("access_token");
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
     /// let result = client.file_list_all("/apps/myapp", 1, None, None).await?;
     /// println!("Total files: {}", result.list.len());
     /// # Ok(())
@@ -24,7 +37,7 @@ impl BaiduPanClient {
     /// ```
     pub async fn file_list_all(
         &self,
-        path: &str,
+        path: impl ToRemotePath,
         recursion: i32,
         start: Option<i32>,
         limit: Option<i32>,
@@ -33,7 +46,7 @@ impl BaiduPanClient {
 
         let mut params = HashMap::new();
         params.insert("method".to_string(), "listall".to_string());
-        params.insert("path".to_string(), path.to_string());
+        params.insert("path".to_string(), path.to_remote_path()?.into());
         params.insert("recursion".to_string(), recursion.to_string());
         params.insert("openapi".to_string(), "xpansdk".to_string());
 
@@ -48,6 +61,43 @@ impl BaiduPanClient {
         BaiduPanClient::parse_response(response).await
     }
 
+    /// 逐项列出目录中的文件，自动翻页
+    ///
+    /// 按服务器返回的 `has_more` 和 `cursor` 按需请求下一页，没有更多数据时结束。
+    /// 请求失败时流返回该错误后结束。用法参见 [`BaiduPanClient::file_list_stream`]。
+    ///
+    /// # 参数
+    /// - `path`: 目录路径
+    /// - `options`: 列表选项，`recursive` 决定是否递归列出子目录
+    pub fn file_list_all_stream(
+        &self,
+        path: impl ToRemotePath,
+        options: &ListOptions,
+    ) -> impl Stream<Item = Result<FileInfo>> + 'static {
+        let path = match path.to_remote_path() {
+            Ok(path) => path,
+            Err(e) => return Either::Left(stream::once(async { Err(e) })),
+        };
+        let client = self.clone();
+        let recursion = options.recursive as i32;
+        let page_size = options.page_limit();
+
+        Either::Right(paginate(move |start| {
+            let client = client.clone();
+            let path = path.clone();
+            async move {
+                let page = client
+                    .file_list_all(&path, recursion, Some(start), Some(page_size))
+                    .await?;
+                let next = match (page.has_more, page.cursor) {
+                    (Some(1), Some(cursor)) => cursor.parse().ok(),
+                    _ => None,
+                };
+                Ok((page.list, next))
+            }
+        }))
+    }
+
     /// 获取多媒体文件元数据
     ///
     /// 此接口可以获取文件的详细信息，包括：
@@ -65,9 +115,9 @@ impl BaiduPanClient {
     ///
     /// # 示例
     /// ```no_run
-    /// # use baidupan_sdk_rust::BaiduPanClient;
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = BaiduPanClient::new("access_token");
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
     /// // 获取文件下载链接
     /// let result = client.file_metas("[123456,789012]", Some(1), None, None, None).await?;
     /// for item in result.list {
@@ -109,4 +159,83 @@ impl BaiduPanClient {
         let response = self.get(&url, params).await?;
         BaiduPanClient::parse_response(response).await
     }
+
+    /// 获取带下载链接的文件元数据(带缓存)
+    ///
+    /// dlink 有效期为 8 小时，缓存的 dlink 在过期前会被复用，过期后自动重新获取。
+    ///
+    /// # 参数
+    /// - `fs_id`: 文件 ID
+    pub async fn dlink_meta(&self, fs_id: i64) -> Result<FileMetaInfo> {
+        self.dlink_metas(&[fs_id])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::ApiError {
+                errno: -9,
+                message: format!("未获取到文件 {} 的下载链接", fs_id),
+            })
+    }
+
+    /// 批量获取带下载链接的文件元数据(带缓存)
+    ///
+    /// 未缓存或已过期的文件按每批 100 个调用 `file_metas` 获取。
+    /// 返回结果按 `fs_ids` 的顺序排列，未获取到下载链接的文件(如目录)不包含在结果中。
+    ///
+    /// # 参数
+    /// - `fs_ids`: 文件 ID 列表
+    pub async fn dlink_metas(&self, fs_ids: &[i64]) -> Result<Vec<FileMetaInfo>> {
+        let missing: Vec<i64> = match self.dlinks.lock() {
+            Ok(dlinks) => fs_ids
+                .iter()
+                .copied()
+                .filter(|id| {
+                    dlinks
+                        .get(id)
+                        .is_none_or(|(_, fetched_at)| fetched_at.elapsed() >= DLINK_TTL)
+                })
+                .collect(),
+            Err(_) => fs_ids.to_vec(),
+        };
+
+        let mut fetched = HashMap::new();
+        for batch in missing.chunks(FILE_METAS_BATCH_SIZE) {
+            let ids: Vec<String> = batch.iter().map(|id| id.to_string()).collect();
+            let metas = self
+                .file_metas(&format!("[{}]", ids.join(",")), Some(1), None, None, None)
+                .await?;
+            for meta in metas.list {
+                if let (Some(id), Some(_)) = (meta.fs_id, &meta.dlink) {
+                    fetched.insert(id, meta);
+                }
+            }
+        }
+
+        let Ok(mut dlinks) = self.dlinks.lock() else {
+            return Ok(fs_ids.iter().filter_map(|id| fetched.remove(id)).collect());
+        };
+        let now = Instant::now();
+        for (id, meta) in fetched {
+            dlinks.insert(id, (meta, now));
+        }
+        Ok(fs_ids
+            .iter()
+            .filter_map(|id| dlinks.get(id).map(|(meta, _)| meta.clone()))
+            .collect())
+    }
+
+    /// 使缓存的下载链接失效，下次调用 `dlink_meta` 时重新获取
+    pub fn invalidate_dlink(&self, fs_id: i64) {
+        if let Ok(mut dlinks) = self.dlinks.lock() {
+            dlinks.remove(&fs_id);
+        }
+    }
+
+    /// 递归列出目录下的所有文件和子目录(自动翻页)
+    pub(crate) async fn list_all_recursive(&self, path: &RemotePath) -> Result<Vec<FileInfo>> {
+        let options = ListOptions::new().with_recursive(true);
+        self.file_list_all_stream(path, &options)
+            .try_collect()
+            .await
+    }
 }
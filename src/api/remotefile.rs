@@ -0,0 +1,302 @@
+use crate::api::filedownload::{MAX_DLINK_REFRESHES, is_dlink_rejected};
+use crate::models::download::RemoteFileOptions;
+use crate::models::path::ToRemotePath;
+use crate::{BaiduPanClient, Error, Result};
+use bytes::Bytes;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+/// 正在进行的块读取请求，完成后返回 (起始块序号, 数据)
+type BlockFetch = Pin<Box<dyn Future<Output = Result<(u64, Bytes)>> + Send>>;
+
+/// 按块缓存的 LRU
+struct BlockCache {
+    capacity: usize,
+    blocks: HashMap<u64, Bytes>,
+    /// 最近使用的块排在末尾
+    order: VecDeque<u64>,
+}
+
+impl BlockCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn contains(&self, index: u64) -> bool {
+        self.blocks.contains_key(&index)
+    }
+
+    /// 读取块并标记为最近使用
+    fn get(&mut self, index: u64) -> Option<Bytes> {
+        let block = self.blocks.get(&index)?.clone();
+        self.touch(index);
+        Some(block)
+    }
+
+    fn insert(&mut self, index: u64, block: Bytes) {
+        if self.blocks.insert(index, block).is_some() {
+            self.touch(index);
+            return;
+        }
+        self.order.push_back(index);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.blocks.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, index: u64) {
+        if let Some(pos) = self.order.iter().position(|&i| i == index) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(index);
+    }
+}
+
+/// 可随机读取的远程文件
+///
+/// 实现了 [`AsyncRead`] 和 [`AsyncSeek`]，可以直接交给现有的解析器读取网盘文件，
+/// 例如读取 zip 中央目录、parquet 文件尾或视频的 moov 信息。
+///
+/// 读到未缓存的块时发起 Range 请求，一次最多连续读取 [`RemoteFileOptions::fetch_blocks`] 个块，
+/// 读到的块保存在 LRU 缓存中。请求只在读取时发起，不会在后台预取。dlink 过期时会自动重新获取。
+///
+/// 通过 [`BaiduPanClient::open_remote_path`] 或 [`BaiduPanClient::open_remote_fsid`] 打开。
+pub struct RemoteFile {
+    client: BaiduPanClient,
+    fs_id: i64,
+    size: u64,
+    position: u64,
+    block_size: u64,
+    fetch_blocks: u64,
+    cache: BlockCache,
+    pending: Option<BlockFetch>,
+}
+
+impl fmt::Debug for RemoteFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteFile")
+            .field("fs_id", &self.fs_id)
+            .field("size", &self.size)
+            .field("position", &self.position)
+            .field("block_size", &self.block_size)
+            .field("fetch_blocks", &self.fetch_blocks)
+            .field("cached_blocks", &self.cache.blocks.len())
+            .finish()
+    }
+}
+
+impl BaiduPanClient {
+    /// 按远程路径打开文件用于随机读取
+    ///
+    /// # 参数
+    /// - `remote_path`: 远程文件路径
+    /// - `options`: 随机读取选项
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// # use baidupan_sdk_rust::models::download::RemoteFileOptions;
+    /// use std::io::SeekFrom;
+    /// use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BaiduPanClient::new(Config::new("access_token"));
+    ///
+    /// let mut file = client
+    ///     .open_remote_path("/apps/myapp/archive.zip", RemoteFileOptions::new())
+    ///     .await?;
+    /// // 读取文件末尾的 22 字节(zip 中央目录结束记录)
+    /// file.seek(SeekFrom::End(-22)).await?;
+    /// let mut eocd = [0u8; 22];
+    /// file.read_exact(&mut eocd).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn open_remote_path(
+        &self,
+        remote_path: impl ToRemotePath,
+        options: RemoteFileOptions,
+    ) -> Result<RemoteFile> {
+        let remote_path = remote_path.to_remote_path()?;
+        let info = self.file_info_by_path(&remote_path).await?;
+        if info.isdir == Some(1) {
+            return Err(Error::ParamError(format!("不能打开目录: {}", remote_path)));
+        }
+        let fs_id = info
+            .fs_id
+            .ok_or_else(|| Error::Other(format!("未获取到文件 ID: {}", remote_path)))?;
+
+        self.open_remote_fsid(fs_id, options).await
+    }
+
+    /// 按文件 ID 打开文件用于随机读取
+    ///
+    /// # 参数
+    /// - `fs_id`: 文件 ID
+    /// - `options`: 随机读取选项
+    pub async fn open_remote_fsid(
+        &self,
+        fs_id: i64,
+        options: RemoteFileOptions,
+    ) -> Result<RemoteFile> {
+        let meta = self.dlink_meta(fs_id).await?;
+        let size = meta
+            .size
+            .ok_or_else(|| Error::Other(format!("未获取到文件 {} 的大小", fs_id)))?;
+        let fetch_blocks = options.fetch_blocks.max(1);
+
+        Ok(RemoteFile {
+            client: self.clone(),
+            fs_id,
+            size: size.max(0) as u64,
+            position: 0,
+            block_size: options.block_size.max(1),
+            fetch_blocks: fetch_blocks as u64,
+            // 缓存至少能容纳一次请求读到的所有块
+            cache: BlockCache::new(options.cache_blocks.max(fetch_blocks)),
+            pending: None,
+        })
+    }
+}
+
+impl RemoteFile {
+    /// 文件 ID
+    pub fn fs_id(&self) -> i64 {
+        self.fs_id
+    }
+
+    /// 文件大小
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// 当前读取位置
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 从 `first` 块开始读取，连续读到窗口上限、已缓存的块或文件末尾为止
+    fn fetch(&self, first: u64) -> BlockFetch {
+        let total_blocks = self.size.div_ceil(self.block_size);
+        let mut count = 1;
+        while count < self.fetch_blocks
+            && first + count < total_blocks
+            && !self.cache.contains(first + count)
+        {
+            count += 1;
+        }
+
+        let start = first * self.block_size;
+        let end = ((first + count) * self.block_size).min(self.size) - 1;
+        let client = self.client.clone();
+        let fs_id = self.fs_id;
+
+        Box::pin(async move {
+            let mut refreshes = 0;
+            loop {
+                let meta = client.dlink_meta(fs_id).await?;
+                let dlink = meta
+                    .dlink
+                    .ok_or_else(|| Error::Other(format!("未获取到文件 {} 的下载链接", fs_id)))?;
+                match client.download_range_bytes(&dlink, start, end).await {
+                    Err(e) if is_dlink_rejected(&e) && refreshes < MAX_DLINK_REFRESHES => {
+                        refreshes += 1;
+                        client.invalidate_dlink(fs_id);
+                    }
+                    result => return result.map(|data| (first, data)),
+                }
+            }
+        })
+    }
+
+    /// 把读取到的连续数据按块切分后放入缓存
+    fn store(&mut self, first: u64, data: Bytes) {
+        let block_size = self.block_size as usize;
+        let mut offset = 0;
+        let mut index = first;
+        while offset < data.len() {
+            let len = block_size.min(data.len() - offset);
+            self.cache.insert(index, data.slice(offset..offset + len));
+            offset += len;
+            index += 1;
+        }
+    }
+}
+
+impl AsyncRead for RemoteFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.position >= this.size || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            let index = this.position / this.block_size;
+            if let Some(block) = this.cache.get(index) {
+                let offset = (this.position - index * this.block_size) as usize;
+                let n = buf.remaining().min(block.len() - offset);
+                buf.put_slice(&block[offset..offset + n]);
+                this.position += n as u64;
+                return Poll::Ready(Ok(()));
+            }
+
+            // seek 后正在进行的请求可能不包含当前块，等它完成后再按需重新请求
+            let mut pending = match this.pending.take() {
+                Some(pending) => pending,
+                None => this.fetch(index),
+            };
+            match pending.as_mut().poll(cx) {
+                Poll::Pending => {
+                    this.pending = Some(pending);
+                    return Poll::Pending;
+                }
+                Poll::Ready(result) => {
+                    let (first, data) = result.map_err(io::Error::other)?;
+                    this.store(first, data);
+                }
+            }
+        }
+    }
+}
+
+impl AsyncSeek for RemoteFile {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+        let target = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => this.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+
+        match target {
+            Some(target) => {
+                this.position = target;
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "无效的 seek 位置: 位于文件开头之前",
+            )),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
@@ -0,0 +1,604 @@
+use crate::api::remotefile::RemoteFile;
+use crate::models::download::{
+    DownloadDirOptions, DownloadDirSummary, DownloadOptions, RemoteFileOptions,
+};
+use crate::models::file::{
+    BatchItemResult, BatchOptions, BatchOutcome, FileCreateResponse, FileInfo, FileListResponse,
+    FileMoveItem, FileOperationInfo, FileOperationResponse, OnConflict, OnDuplicate, OperationMode,
+    PendingItem, TaskPollOptions, TaskQueryResponse,
+};
+use crate::models::path::{RemotePath, ToRemotePath};
+use crate::models::upload::{UploadDirOptions, UploadDirSummary, UploadOptions};
+use crate::{BaiduPanClient, Error, Result};
+use std::path::Path;
+
+/// 限定在某个根目录下的客户端
+///
+/// 所有路径参数都相对于根目录解析，返回结果中的路径去掉根目录前缀，成为相对路径：
+/// - 相对路径(如 `docs/a.txt`)拼接到根目录下
+/// - 绝对路径必须位于根目录内，否则返回 [`Error::PathOutsideRoot`]
+/// - 包含 `..` 的路径一律返回 [`Error::PathOutsideRoot`]
+///
+/// 越界的路径在发送请求前就会被拒绝。创建、删除、移动、复制、重命名和上传的目标不能是根目录本身，
+/// 否则返回 [`Error::InvalidPath`]，避免误删或移走整个根目录。适用于只能访问 `/apps/<应用名>` 的应用，
+/// 或需要把每个工作线程限制在一个子目录下的多租户场景。
+///
+/// 只提供按路径操作的方法；按文件 ID 的操作无法判断文件位置，需要时请使用 [`BaiduPanClient`]。
+///
+/// # 示例
+/// ```no_run
+/// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+/// # use baidupan_sdk_rust::models::file::OperationMode;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BaiduPanClient::new(Config::new("access_token"));
+/// let scoped = client.scoped("/apps/myapp/tenant-42")?;
+///
+/// // 实际访问 /apps/myapp/tenant-42/reports
+/// let list = scoped.file_list("reports", None, None, None, None).await?;
+/// for file in &list.list {
+///     // 路径为 reports/xxx，不含根目录
+///     println!("{:?}", file.path);
+/// }
+///
+/// // 越界的路径在发送请求前被拒绝
+/// assert!(scoped.delete_files(&["../other"], OperationMode::Sync).await.is_err());
+/// assert!(scoped.delete_files(&["/apps/other"], OperationMode::Sync).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedClient {
+    client: BaiduPanClient,
+    root: RemotePath,
+}
+
+impl BaiduPanClient {
+    /// 创建限定在 `root` 目录下的客户端
+    ///
+    /// # 参数
+    ///
+    /// * `root` - 根目录
+    pub fn scoped(&self, root: impl ToRemotePath) -> Result<ScopedClient> {
+        Ok(ScopedClient {
+            client: self.clone(),
+            root: root.to_remote_path()?,
+        })
+    }
+}
+
+impl ScopedClient {
+    /// 根目录
+    pub fn root(&self) -> &RemotePath {
+        &self.root
+    }
+
+    /// 把路径解析为根目录下的绝对路径
+    ///
+    /// 空字符串和 `.` 表示根目录本身。
+    pub fn resolve(&self, path: &str) -> Result<RemotePath> {
+        if path.split('/').any(|segment| segment == "..") {
+            return Err(self.outside_root(path));
+        }
+        if !path.starts_with('/') {
+            return self.root.join(path);
+        }
+
+        let resolved = RemotePath::new(path)?;
+        if !resolved.starts_with(&self.root) {
+            return Err(self.outside_root(path));
+        }
+        Ok(resolved)
+    }
+
+    /// 获取文件列表，参数与 [`BaiduPanClient::file_list`] 相同
+    pub async fn file_list(
+        &self,
+        dir: &str,
+        order: Option<&str>,
+        desc: Option<i32>,
+        start: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<FileListResponse> {
+        let dir = self.resolve(dir)?;
+        let response = self
+            .client
+            .file_list(&dir, order, desc, start, limit)
+            .await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 递归获取文件列表，参数与 [`BaiduPanClient::file_list_all`] 相同
+    pub async fn file_list_all(
+        &self,
+        path: &str,
+        recursion: i32,
+        start: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<FileListResponse> {
+        let path = self.resolve(path)?;
+        let response = self
+            .client
+            .file_list_all(&path, recursion, start, limit)
+            .await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 搜索文件，`dir` 为 None 时在根目录下搜索
+    pub async fn file_search(
+        &self,
+        key: &str,
+        dir: Option<&str>,
+        recursion: Option<i32>,
+    ) -> Result<FileListResponse> {
+        let dir = self.resolve(dir.unwrap_or(""))?;
+        let response = self
+            .client
+            .file_search(key, Some(dir.as_str()), recursion)
+            .await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 按路径获取文件信息
+    pub async fn file_info_by_path(&self, path: &str) -> Result<FileInfo> {
+        let path = self.resolve(path)?;
+        let info = self.client.file_info_by_path(&path).await?;
+        Ok(self.unscope(info))
+    }
+
+    /// 创建文件夹
+    pub async fn create_dir(
+        &self,
+        path: &str,
+        on_conflict: OnConflict,
+    ) -> Result<FileCreateResponse> {
+        let path = self.resolve_target(path)?;
+        let response = self.client.create_dir(&path, on_conflict).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 确保目录存在，类似 `mkdir -p`
+    ///
+    /// 只会在根目录内创建目录(包括根目录本身)，不会为根目录的上级目录发送创建请求。
+    pub async fn ensure_dir(&self, path: &str) -> Result<()> {
+        let path = self.resolve(path)?;
+        self.client.ensure_dir_within(path, &self.root).await
+    }
+
+    /// 删除文件
+    pub async fn delete_files(
+        &self,
+        paths: &[impl AsRef<str>],
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let paths = self.resolve_all(paths)?;
+        let response = self.client.delete_files(&paths, mode).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 移动文件，文件项的源路径和目标目录都相对于根目录解析
+    pub async fn move_files(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let items = self.resolve_items(items)?;
+        let response = self.client.move_files(&items, on_duplicate, mode).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 复制文件，文件项的源路径和目标目录都相对于根目录解析
+    pub async fn copy_files(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let items = self.resolve_items(items)?;
+        let response = self.client.copy_files(&items, on_duplicate, mode).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 重命名文件
+    pub async fn rename_file(
+        &self,
+        path: &str,
+        new_name: &str,
+        mode: OperationMode,
+    ) -> Result<FileOperationResponse> {
+        let path = self.resolve_target(path)?;
+        let response = self.client.rename_file(&path, new_name, mode).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 等待异步执行的文件管理任务结束
+    ///
+    /// # 参数
+    ///
+    /// * `taskid` - 文件管理操作返回的任务 ID
+    /// * `options` - 轮询选项
+    pub async fn wait_task(
+        &self,
+        taskid: i64,
+        options: &TaskPollOptions,
+    ) -> Result<TaskQueryResponse> {
+        let response = self.client.file_task(taskid).wait(options).await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 批量删除文件，参见 [`BaiduPanClient::batch_delete`]
+    pub async fn batch_delete(
+        &self,
+        paths: &[impl AsRef<str>],
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<String>> {
+        let paths = self.resolve_all(paths)?;
+        let outcome = self.client.batch_delete(&paths, options).await?;
+        Ok(self.unscope(outcome))
+    }
+
+    /// 批量移动文件，参见 [`BaiduPanClient::batch_move`]
+    pub async fn batch_move(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<FileMoveItem>> {
+        let items = self.resolve_items(items)?;
+        let outcome = self
+            .client
+            .batch_move(&items, on_duplicate, options)
+            .await?;
+        Ok(self.unscope(outcome))
+    }
+
+    /// 批量复制文件，参见 [`BaiduPanClient::batch_copy`]
+    pub async fn batch_copy(
+        &self,
+        items: &[FileMoveItem],
+        on_duplicate: OnDuplicate,
+        options: &BatchOptions,
+    ) -> Result<BatchOutcome<FileMoveItem>> {
+        let items = self.resolve_items(items)?;
+        let outcome = self
+            .client
+            .batch_copy(&items, on_duplicate, options)
+            .await?;
+        Ok(self.unscope(outcome))
+    }
+
+    /// 上传文件，参见 [`BaiduPanClient::upload_file_with_options`]
+    pub async fn upload_file(
+        &self,
+        local_path: impl AsRef<Path>,
+        remote_path: &str,
+        options: &UploadOptions,
+    ) -> Result<FileCreateResponse> {
+        let remote_path = self.resolve_target(remote_path)?;
+        let response = self
+            .client
+            .upload_file_with_options(local_path, &remote_path, options)
+            .await?;
+        Ok(self.unscope(response))
+    }
+
+    /// 上传目录，参见 [`BaiduPanClient::upload_dir`]
+    pub async fn upload_dir(
+        &self,
+        local_dir: impl AsRef<Path>,
+        remote_dir: &str,
+        options: &UploadDirOptions,
+    ) -> Result<UploadDirSummary> {
+        let remote_dir = self.resolve(remote_dir)?;
+        let summary = self
+            .client
+            .upload_dir(local_dir, &remote_dir, options)
+            .await?;
+        Ok(self.unscope(summary))
+    }
+
+    /// 按路径下载文件，参见 [`BaiduPanClient::download_path`]
+    pub async fn download_path(
+        &self,
+        remote_path: &str,
+        save_path: impl AsRef<Path>,
+        options: &DownloadOptions,
+    ) -> Result<u64> {
+        let remote_path = self.resolve(remote_path)?;
+        self.client
+            .download_path(&remote_path, save_path, options)
+            .await
+    }
+
+    /// 下载目录，参见 [`BaiduPanClient::download_dir`]
+    pub async fn download_dir(
+        &self,
+        remote_dir: &str,
+        local_dir: impl AsRef<Path>,
+        options: &DownloadDirOptions,
+    ) -> Result<DownloadDirSummary> {
+        let remote_dir = self.resolve(remote_dir)?;
+        let summary = self
+            .client
+            .download_dir(&remote_dir, local_dir, options)
+            .await?;
+        Ok(self.unscope(summary))
+    }
+
+    /// 打开文件用于随机读取，参见 [`BaiduPanClient::open_remote_path`]
+    pub async fn open_remote_path(
+        &self,
+        remote_path: &str,
+        options: RemoteFileOptions,
+    ) -> Result<RemoteFile> {
+        let remote_path = self.resolve(remote_path)?;
+        self.client.open_remote_path(&remote_path, options).await
+    }
+
+    /// 解析修改操作的目标路径，目标不能是根目录本身
+    fn resolve_target(&self, path: &str) -> Result<RemotePath> {
+        let resolved = self.resolve(path)?;
+        if resolved == self.root {
+            return Err(Error::InvalidPath {
+                path: path.to_string(),
+                reason: format!("不能修改根目录 {} 本身", self.root),
+            });
+        }
+        Ok(resolved)
+    }
+
+    /// 解析多个修改操作的目标路径
+    fn resolve_all(&self, paths: &[impl AsRef<str>]) -> Result<Vec<RemotePath>> {
+        paths
+            .iter()
+            .map(|p| self.resolve_target(p.as_ref()))
+            .collect()
+    }
+
+    /// 解析文件项的源路径和目标目录，源路径不能是根目录本身
+    fn resolve_items(&self, items: &[FileMoveItem]) -> Result<Vec<FileMoveItem>> {
+        items
+            .iter()
+            .map(|item| {
+                Ok(FileMoveItem {
+                    path: self.resolve_target(&item.path)?.into(),
+                    dest: self.resolve(&item.dest)?.into(),
+                    newname: item.newname.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// 去掉结果中的根目录前缀
+    fn unscope<T: Unscope>(&self, mut value: T) -> T {
+        value.unscope(&self.root);
+        value
+    }
+
+    fn outside_root(&self, path: &str) -> Error {
+        Error::PathOutsideRoot {
+            path: path.to_string(),
+            root: self.root.to_string(),
+        }
+    }
+}
+
+/// 结果中需要去掉根目录前缀的路径
+trait Unscope {
+    fn unscope(&mut self, root: &RemotePath);
+}
+
+impl Unscope for String {
+    fn unscope(&mut self, root: &RemotePath) {
+        let relative = RemotePath::new(self)
+            .ok()
+            .and_then(|path| path.strip_prefix(root).map(str::to_string));
+        if let Some(relative) = relative {
+            *self = relative;
+        }
+    }
+}
+
+impl<T: Unscope> Unscope for Option<T> {
+    fn unscope(&mut self, root: &RemotePath) {
+        if let Some(value) = self {
+            value.unscope(root);
+        }
+    }
+}
+
+impl<T: Unscope> Unscope for Vec<T> {
+    fn unscope(&mut self, root: &RemotePath) {
+        for value in self {
+            value.unscope(root);
+        }
+    }
+}
+
+impl Unscope for FileInfo {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.path.unscope(root);
+    }
+}
+
+impl Unscope for FileListResponse {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.list.unscope(root);
+    }
+}
+
+impl Unscope for FileCreateResponse {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.path.unscope(root);
+    }
+}
+
+impl Unscope for FileOperationInfo {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.path.unscope(root);
+        self.from.unscope(root);
+        self.to.unscope(root);
+    }
+}
+
+impl Unscope for FileOperationResponse {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.info.unscope(root);
+    }
+}
+
+impl Unscope for TaskQueryResponse {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.list.unscope(root);
+    }
+}
+
+impl Unscope for FileMoveItem {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.path.unscope(root);
+        self.dest.unscope(root);
+    }
+}
+
+impl<T: Unscope> Unscope for BatchItemResult<T> {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.item.unscope(root);
+        self.source.unscope(root);
+        self.destination.unscope(root);
+        self.new_path.unscope(root);
+    }
+}
+
+impl<T: Unscope> Unscope for PendingItem<T> {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.item.unscope(root);
+        self.source.unscope(root);
+        self.destination.unscope(root);
+    }
+}
+
+impl<T: Unscope> Unscope for BatchOutcome<T> {
+    fn unscope(&mut self, root: &RemotePath) {
+        self.succeeded.unscope(root);
+        self.failed.unscope(root);
+        self.pending.unscope(root);
+    }
+}
+
+impl Unscope for UploadDirSummary {
+    fn unscope(&mut self, root: &RemotePath) {
+        for file in self.uploaded.iter_mut().chain(&mut self.rapid_uploaded) {
+            file.remote_path.unscope(root);
+            file.response.unscope(root);
+        }
+        for failure in &mut self.failed {
+            failure.remote_path.unscope(root);
+        }
+    }
+}
+
+impl Unscope for DownloadDirSummary {
+    fn unscope(&mut self, root: &RemotePath) {
+        for file in &mut self.downloaded {
+            file.remote_path.unscope(root);
+        }
+        for failure in &mut self.failed {
+            failure.remote_path.unscope(root);
+        }
+        for entry in &mut self.renamed {
+            entry.remote_path.unscope(root);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    fn scoped() -> ScopedClient {
+        BaiduPanClient::new(Config::new("token"))
+            .scoped("/apps/myapp/tenant")
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_joins_relative_paths_under_root() {
+        let scoped = scoped();
+        assert_eq!(
+            scoped.resolve("docs/a.txt").unwrap().as_str(),
+            "/apps/myapp/tenant/docs/a.txt"
+        );
+        assert_eq!(scoped.resolve("").unwrap(), *scoped.root());
+        assert_eq!(scoped.resolve(".").unwrap(), *scoped.root());
+        assert_eq!(
+            scoped.resolve("/apps/myapp/tenant/x").unwrap().as_str(),
+            "/apps/myapp/tenant/x"
+        );
+    }
+
+    #[test]
+    fn resolve_rejects_parent_segments() {
+        let scoped = scoped();
+        for path in [
+            "..",
+            "../other",
+            "docs/../../other",
+            "docs/..",
+            "/apps/myapp/tenant/../x",
+        ] {
+            assert!(
+                matches!(scoped.resolve(path), Err(Error::PathOutsideRoot { .. })),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_rejects_absolute_paths_outside_root() {
+        let scoped = scoped();
+        for path in [
+            "/",
+            "/apps/myapp",
+            "/apps/myapp/tenant2",
+            "/apps/myapp/tenan",
+            "/other",
+        ] {
+            assert!(
+                matches!(scoped.resolve(path), Err(Error::PathOutsideRoot { .. })),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn mutating_targets_cannot_be_root() {
+        let scoped = scoped();
+        for path in ["", ".", "/apps/myapp/tenant", "/apps/myapp/tenant/"] {
+            assert!(
+                matches!(scoped.resolve_target(path), Err(Error::InvalidPath { .. })),
+                "{path}"
+            );
+        }
+        assert!(scoped.resolve_target("docs").is_ok());
+
+        assert!(
+            scoped
+                .resolve_items(&[FileMoveItem::new("", "backup")])
+                .is_err()
+        );
+        assert!(
+            scoped
+                .resolve_items(&[FileMoveItem::new("a.txt", "")])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn unscope_strips_root_prefix() {
+        let scoped = scoped();
+        let mut path = "/apps/myapp/tenant/docs/a.txt".to_string();
+        path.unscope(scoped.root());
+        assert_eq!(path, "docs/a.txt");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::user::{QuotaResponse, UserInfoResponse};
+use crate::models::user::{QuotaResponse, UserInfoResponse, VipTier};
 use crate::{BaiduPanClient, Result};
 use std::collections::HashMap;
 
@@ -15,6 +15,20 @@ impl BaiduPanClient {
         BaiduPanClient::parse_response(response).await
     }
 
+    /// 获取用户信息(带缓存)
+    ///
+    /// 首次调用时请求 `get_user_info`，之后在同一客户端(及其克隆)内复用结果。
+    pub async fn cached_user_info(&self) -> Result<&UserInfoResponse> {
+        self.user_info
+            .get_or_try_init(|| self.get_user_info())
+            .await
+    }
+
+    /// 获取当前账号的会员等级(带缓存)
+    pub async fn vip_tier(&self) -> Result<VipTier> {
+        Ok(self.cached_user_info().await?.vip_tier())
+    }
+
     /// 获取配额信息
     pub async fn get_quota(&self) -> Result<QuotaResponse> {
         let url = format!("{}/api/quota", self.config.server.pan_url);
@@ -0,0 +1,331 @@
+use crate::{BaiduPanClient, Error, Result};
+use serde::Serialize;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 审计日志文件的默认大小上限
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// 操作结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// 成功
+    Success,
+
+    /// 部分文件失败
+    PartialFailure,
+
+    /// 失败
+    Failure,
+}
+
+/// 一条审计记录，写入时序列化为一行 JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    /// 时间(Unix 毫秒)
+    pub timestamp: i64,
+
+    /// 账号 UK，无法获取用户信息时为 None
+    pub uk: Option<i64>,
+
+    /// 操作: `mkdir`、`delete`、`move`、`copy`、`rename`、`precreate`、`create` 或 `rapid_upload`
+    pub operation: String,
+
+    /// 操作的文件路径
+    pub paths: Vec<String>,
+
+    /// 移动、复制或重命名的目标路径，与 `paths` 一一对应
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub destinations: Vec<String>,
+
+    /// 服务器返回的文件 ID
+    pub fs_ids: Vec<i64>,
+
+    /// 操作结果
+    pub outcome: AuditOutcome,
+
+    /// 错误码，0 表示成功；请求失败且没有错误码时为 -1
+    pub errno: i32,
+
+    /// 服务器返回的请求 ID
+    pub request_id: Option<String>,
+}
+
+impl AuditRecord {
+    /// 创建成功状态的记录，时间为当前时间
+    pub(crate) fn new(operation: &str, paths: Vec<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        Self {
+            timestamp,
+            uk: None,
+            operation: operation.to_string(),
+            paths,
+            destinations: Vec::new(),
+            fs_ids: Vec::new(),
+            outcome: AuditOutcome::Success,
+            errno: 0,
+            request_id: None,
+        }
+    }
+
+    /// 设置目标路径
+    pub(crate) fn with_destinations(mut self, destinations: Vec<String>) -> Self {
+        self.destinations = destinations;
+        self
+    }
+
+    /// 记录成功返回的文件 ID 和请求 ID
+    pub(crate) fn succeeded(mut self, fs_ids: Vec<i64>, request_id: Option<String>) -> Self {
+        self.fs_ids = fs_ids;
+        self.request_id = request_id;
+        self
+    }
+
+    /// 按请求结果记录，成功时由 `ids` 取出文件 ID 和请求 ID
+    pub(crate) fn with_result<T>(
+        self,
+        result: &Result<T>,
+        ids: impl FnOnce(&T) -> (Vec<i64>, Option<String>),
+    ) -> Self {
+        match result {
+            Ok(value) => {
+                let (fs_ids, request_id) = ids(value);
+                self.succeeded(fs_ids, request_id)
+            }
+            Err(error) => self.failed(error),
+        }
+    }
+
+    /// 记录部分文件失败时的整体错误码
+    pub(crate) fn partially_failed(mut self, errno: i32) -> Self {
+        self.outcome = AuditOutcome::PartialFailure;
+        self.errno = errno;
+        self
+    }
+
+    /// 记录失败原因
+    pub(crate) fn failed(mut self, error: &Error) -> Self {
+        self.outcome = AuditOutcome::Failure;
+        self.errno = error.errno().unwrap_or(-1);
+        self
+    }
+}
+
+/// 审计日志的写入目标
+///
+/// 每次修改网盘内容的请求结束后(无论成功与否)调用一次 [`AuditSink::record`]。
+/// 写入失败不会影响操作本身的结果，失败次数通过
+/// [`BaiduPanClient::audit_failures`](crate::BaiduPanClient::audit_failures) 查询，
+/// 启用调试模式时同时输出到标准错误。
+pub trait AuditSink: Send + Sync {
+    /// 写入一条记录
+    fn record(&self, record: &AuditRecord) -> io::Result<()>;
+}
+
+impl fmt::Debug for dyn AuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuditSink")
+    }
+}
+
+/// 写入文件的审计日志，按大小轮转
+///
+/// 文件超过 `max_bytes` 时重命名为 `<path>.1`，已有的历史文件序号依次加 1(序号越大越早)。
+/// 默认保留全部历史文件；只有通过 [`FileAuditSink::with_max_files`] 显式设置上限后，
+/// 才会删除超出数量的最早文件。只会追加写入，不会修改已有内容。
+#[derive(Debug)]
+pub struct FileAuditSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: Option<usize>,
+    state: Mutex<Option<(File, u64)>>,
+}
+
+impl FileAuditSink {
+    /// 创建写入 `path` 的审计日志，文件在第一次写入时创建
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_files: None,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// 设置单个日志文件的大小上限
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// 设置保留的历史日志文件数量，超出时删除最早的文件
+    ///
+    /// 默认不删除任何历史文件。当前正在写入的日志文件不计入数量，也不会被删除，
+    /// 因此 `max_files` 至少为 1，传入 0 时按 1 处理。
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files.max(1));
+        self
+    }
+
+    /// 以追加方式打开日志文件，返回文件和当前大小
+    fn open(&self) -> io::Result<(File, u64)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let size = file.metadata()?.len();
+        Ok((file, size))
+    }
+
+    /// 把当前文件重命名为 `.1`，已有的历史文件序号依次加 1
+    ///
+    /// 设置了 `max_files` 时删除序号超出上限的文件。
+    fn rotate(&self) -> io::Result<()> {
+        let mut count = 0;
+        while rotated_path(&self.path, count + 1).exists() {
+            count += 1;
+        }
+
+        if let Some(max_files) = self.max_files {
+            while count >= max_files {
+                fs::remove_file(rotated_path(&self.path, count))?;
+                count -= 1;
+            }
+        }
+        for index in (1..=count).rev() {
+            fs::rename(
+                rotated_path(&self.path, index),
+                rotated_path(&self.path, index + 1),
+            )?;
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&self, record: &AuditRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| io::Error::other("审计日志锁已损坏"))?;
+
+        if state.is_none() {
+            *state = Some(self.open()?);
+        }
+        if let Some((_, size)) = state.as_ref()
+            && *size > 0
+            && size + line.len() as u64 > self.max_bytes
+        {
+            *state = None;
+            self.rotate()?;
+            *state = Some(self.open()?);
+        }
+
+        if let Some((file, size)) = state.as_mut() {
+            file.write_all(&line)?;
+            *size += line.len() as u64;
+        }
+        Ok(())
+    }
+}
+
+/// 历史日志文件路径
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// 写入任意 [`Write`] 的审计日志，例如标准输出或网络连接
+#[derive(Debug)]
+pub struct WriterAuditSink<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriterAuditSink<W> {
+    /// 创建写入 `writer` 的审计日志
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<W: Write + Send> AuditSink for WriterAuditSink<W> {
+    fn record(&self, record: &AuditRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| io::Error::other("审计日志锁已损坏"))?;
+        writer.write_all(&line)?;
+        writer.flush()
+    }
+}
+
+impl BaiduPanClient {
+    /// 设置审计日志
+    ///
+    /// 设置后，创建目录、删除、移动、复制、重命名、预创建/创建文件和秒传每次请求结束后
+    /// 都会写入一条记录。试运行模式和只读模式下不会发送请求，因此不产生记录。
+    ///
+    /// # 示例
+    /// ```no_run
+    /// # use baidupan_sdk_rust::{BaiduPanClient, Config};
+    /// use baidupan_sdk_rust::audit::FileAuditSink;
+    ///
+    /// // 单个文件超过 50MB 时轮转，保留全部历史文件
+    /// let sink = FileAuditSink::new("/var/log/pan-audit.jsonl").with_max_bytes(50 * 1024 * 1024);
+    /// let client = BaiduPanClient::new(Config::new("access_token")).with_audit_sink(sink);
+    /// ```
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// 审计记录写入失败的次数(克隆出的客户端共享计数)
+    ///
+    /// 需要确保审计完整时，可在操作后检查该值是否增加。
+    pub fn audit_failures(&self) -> u64 {
+        self.audit_failures.load(Ordering::Relaxed)
+    }
+
+    /// 补全账号信息后写入审计记录
+    ///
+    /// 账号 UK 只查询一次，查询失败时之后的记录都不带 UK，不会每次重新请求。
+    /// 写入在阻塞线程池中执行，不占用异步运行时的工作线程。
+    pub(crate) async fn audit(&self, mut record: AuditRecord) {
+        let Some(sink) = self.audit_sink.clone() else {
+            return;
+        };
+
+        record.uk = *self
+            .audit_uk
+            .get_or_init(|| async { self.cached_user_info().await.ok().and_then(|info| info.uk) })
+            .await;
+        let result = tokio::task::spawn_blocking(move || sink.record(&record))
+            .await
+            .unwrap_or_else(|e| Err(io::Error::other(e)));
+        if let Err(e) = result {
+            self.audit_failures.fetch_add(1, Ordering::Relaxed);
+            if self.config.debug {
+                eprintln!("写入审计日志失败: {}", e);
+            }
+        }
+    }
+}
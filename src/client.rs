@@ -1,8 +1,16 @@
+use crate::audit::AuditSink;
+use crate::config::{ClientMode, PlannedOperation};
+use crate::models::file::FileMetaInfo;
+use crate::models::user::UserInfoResponse;
 use crate::{Config, Error, Result};
 use reqwest::{Client, Response};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+use tokio::sync::OnceCell;
 
 /// 百度网盘客户端
 #[derive(Debug, Clone)]
@@ -12,6 +20,30 @@ pub struct BaiduPanClient {
 
     /// 配置
     pub(crate) config: Config,
+
+    /// 用户信息缓存
+    pub(crate) user_info: Arc<OnceCell<UserInfoResponse>>,
+
+    /// 上传失败的服务器及标记时间
+    pub(crate) unhealthy_hosts: Arc<Mutex<HashMap<String, Instant>>>,
+
+    /// 下载链接缓存: fs_id -> (带 dlink 的元数据, 获取时间)
+    pub(crate) dlinks: Arc<Mutex<HashMap<i64, (FileMetaInfo, Instant)>>>,
+
+    /// 本次会话中已确认存在的远程目录
+    pub(crate) known_dirs: Arc<Mutex<HashSet<String>>>,
+
+    /// 审计日志
+    pub(crate) audit_sink: Option<Arc<dyn AuditSink>>,
+
+    /// 审计记录中的账号 UK，只查询一次(查询失败时为 None)
+    pub(crate) audit_uk: Arc<OnceCell<Option<i64>>>,
+
+    /// 写入失败的审计记录数量
+    pub(crate) audit_failures: Arc<AtomicU64>,
+
+    /// 试运行模式下拦截的修改操作
+    pub(crate) dry_run_log: Arc<Mutex<Vec<PlannedOperation>>>,
 }
 
 /// API 响应基础结构
@@ -46,6 +78,14 @@ impl BaiduPanClient {
         Self {
             http_client,
             config,
+            user_info: Arc::new(OnceCell::new()),
+            unhealthy_hosts: Arc::new(Mutex::new(HashMap::new())),
+            dlinks: Arc::new(Mutex::new(HashMap::new())),
+            known_dirs: Arc::new(Mutex::new(HashSet::new())),
+            audit_sink: None,
+            audit_uk: Arc::new(OnceCell::new()),
+            audit_failures: Arc::new(AtomicU64::new(0)),
+            dry_run_log: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -54,6 +94,51 @@ impl BaiduPanClient {
         &self.config
     }
 
+    /// 试运行模式下已拦截的修改操作，按执行顺序排列
+    ///
+    /// 克隆出的客户端共享同一份记录。
+    pub fn dry_run_log(&self) -> Vec<PlannedOperation> {
+        self.planned_operations().clone()
+    }
+
+    /// 取出并清空试运行记录
+    pub fn take_dry_run_log(&self) -> Vec<PlannedOperation> {
+        std::mem::take(&mut *self.planned_operations())
+    }
+
+    /// 锁定试运行记录，锁中毒时仍然可用
+    fn planned_operations(&self) -> MutexGuard<'_, Vec<PlannedOperation>> {
+        self.dry_run_log
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 按客户端模式检查修改操作
+    ///
+    /// 只读模式返回错误；试运行模式把每个操作对象记录到 [`Self::dry_run_log`] 并返回 true，
+    /// 调用方应返回模拟结果而不发送请求，启用调试模式时同时输出每个操作对象。
+    pub(crate) fn check_mutation(&self, operation: &str, targets: &[String]) -> Result<bool> {
+        match self.config.mode {
+            ClientMode::Normal => Ok(false),
+            ClientMode::ReadOnly => Err(Error::ReadOnlyMode {
+                operation: operation.to_string(),
+            }),
+            ClientMode::DryRun => {
+                let mut log = self.planned_operations();
+                for target in targets {
+                    if self.config.debug {
+                        eprintln!("[试运行] {} {}", operation, target);
+                    }
+                    log.push(PlannedOperation {
+                        operation: operation.to_string(),
+                        target: target.clone(),
+                    });
+                }
+                Ok(true)
+            }
+        }
+    }
+
     /// 发送 GET 请求
     pub(crate) async fn get(&self, url: &str, params: HashMap<String, String>) -> Result<Response> {
         let mut url = url::Url::parse(url)?;
@@ -21,6 +21,38 @@ impl Default for ServerConfig {
     }
 }
 
+/// 客户端模式，决定修改网盘内容的操作如何执行
+///
+/// 受影响的操作包括创建目录、删除、移动、复制、重命名和上传
+/// (`file_precreate`、`file_create`、`upload_slice` 以及基于它们的上传方法)。
+/// 查询和下载不受影响。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClientMode {
+    /// 正常执行
+    #[default]
+    Normal,
+
+    /// 只读，修改操作返回 [`Error::ReadOnlyMode`](crate::Error::ReadOnlyMode)
+    ReadOnly,
+
+    /// 试运行，修改操作不发送请求，返回模拟的结果
+    ///
+    /// 将要执行的操作记录在客户端中，可通过
+    /// [`BaiduPanClient::dry_run_log`](crate::BaiduPanClient::dry_run_log) 查看；
+    /// 启用调试模式时同时输出到标准错误。
+    DryRun,
+}
+
+/// 试运行模式下拦截的一次修改操作
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlannedOperation {
+    /// 操作名称，如 `delete`、`move`、`upload`
+    pub operation: String,
+
+    /// 操作对象，如远程路径或 `源路径 -> 目标路径`
+    pub target: String,
+}
+
 /// SDK 配置
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -38,6 +70,9 @@ pub struct Config {
 
     /// 是否启用调试模式
     pub debug: bool,
+
+    /// 客户端模式
+    pub mode: ClientMode,
 }
 
 impl Config {
@@ -53,6 +88,7 @@ impl Config {
             timeout: Duration::from_secs(120),
             user_agent: format!("bd-sdk-rust/{}", env!("CARGO_PKG_VERSION")),
             debug: false,
+            mode: ClientMode::default(),
         }
     }
 
@@ -79,4 +115,10 @@ impl Config {
         self.debug = debug;
         self
     }
+
+    /// 设置客户端模式
+    pub fn with_mode(mut self, mode: ClientMode) -> Self {
+        self.mode = mode;
+        self
+    }
 }
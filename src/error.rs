@@ -1,3 +1,4 @@
+use crate::models::user::VipTier;
 use thiserror::Error;
 
 /// SDK 错误类型
@@ -19,6 +20,58 @@ pub enum Error {
     #[error("参数错误: {0}")]
     ParamError(String),
 
+    /// 远程路径不符合网盘的规则
+    #[error("无效的路径 {path}: {reason}")]
+    InvalidPath { path: String, reason: String },
+
+    /// 路径位于 [`ScopedClient`](crate::api::scoped::ScopedClient) 的根目录之外
+    #[error("路径 {path} 超出根目录 {root}")]
+    PathOutsideRoot { path: String, root: String },
+
+    /// 只读模式下拒绝执行修改操作
+    #[error("只读模式下不允许执行 {operation}")]
+    ReadOnlyMode { operation: String },
+
+    /// 文件超出当前会员等级的单文件大小上限
+    #[error("文件大小 {size} 字节超出{tier}单文件上限 {max_size} 字节")]
+    FileTooLarge {
+        size: u64,
+        max_size: u64,
+        tier: VipTier,
+    },
+
+    /// 分片数量超出上限
+    #[error("分片数量 {count} 超出上限 {max}")]
+    TooManySlices { count: u64, max: u64 },
+
+    /// 分片 MD5 校验失败
+    #[error("分片 {partseq} MD5 校验失败: 本地 {expected}, 服务器 {actual}")]
+    SliceMd5Mismatch {
+        partseq: i32,
+        expected: String,
+        actual: String,
+    },
+
+    /// 文件大小与预期不一致
+    #[error("文件大小不一致: 预期 {expected} 字节, 实际 {actual} 字节")]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    /// 文件 MD5 与服务器不一致
+    #[error("文件 MD5 不一致: 预期 {expected}, 实际 {actual}")]
+    Md5Mismatch { expected: String, actual: String },
+
+    /// 路径已被文件占用，无法作为目录使用
+    #[error("路径已存在但不是目录: {path}")]
+    NotADirectory { path: String },
+
+    /// 列表数量超过上限
+    #[error("列表超过 {max_items} 项的上限")]
+    TooManyItems { max_items: usize },
+
+    /// 等待异步任务超时
+    #[error("等待任务 {taskid} 超时")]
+    TaskTimeout { taskid: i64 },
+
     /// URL 解析错误
     #[error("URL 解析失败: {0}")]
     UrlParseError(#[from] url::ParseError),
@@ -34,3 +87,13 @@ pub enum Error {
 
 /// SDK Result 类型
 pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// 获取 API 错误码，非 API 错误时返回 None
+    pub fn errno(&self) -> Option<i32> {
+        match self {
+            Error::ApiError { errno, .. } => Some(*errno),
+            _ => None,
+        }
+    }
+}
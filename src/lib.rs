@@ -12,7 +12,7 @@
 //! # 示例
 //!
 //! ```no_run
-//! use bd_sdk_rust::{BaiduPanClient, Config};
+//! use baidupan_sdk_rust::{BaiduPanClient, Config};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,11 +25,12 @@
 //! ```
 
 pub mod api;
+pub mod audit;
 pub mod client;
 pub mod config;
 pub mod error;
 pub mod models;
 
 pub use client::BaiduPanClient;
-pub use config::Config;
+pub use config::{ClientMode, Config, PlannedOperation};
 pub use error::{Error, Result};
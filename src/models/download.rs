@@ -0,0 +1,548 @@
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// 分段重试的最长等待时间
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// 下载的字节范围(两端均包含)，对应 HTTP `Range` 请求头
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ByteRange {
+    /// 起始字节位置，None 表示从文件开头(配合 `end` 时表示取末尾 `end` 个字节)
+    pub start: Option<u64>,
+
+    /// 结束字节位置，None 表示到文件末尾
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// 指定起止位置的范围 `[start, end]`
+    pub fn new(start: u64, end: u64) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+
+    /// 从 `start` 到文件末尾
+    pub fn starting_at(start: u64) -> Self {
+        Self {
+            start: Some(start),
+            end: None,
+        }
+    }
+
+    /// 文件末尾的 `len` 个字节
+    pub fn suffix(len: u64) -> Self {
+        Self {
+            start: None,
+            end: Some(len),
+        }
+    }
+
+    /// 构建 Range 请求头字符串
+    ///
+    /// - `Some(start), Some(end)` → `bytes=start-end`
+    /// - `Some(start), None`      → `bytes=start-`
+    /// - `None, Some(end)`        → `bytes=-end`
+    /// - `None, None`             → `bytes=0-`
+    pub(crate) fn header_value(&self) -> String {
+        match (self.start, self.end) {
+            (Some(s), Some(e)) => format!("bytes={}-{}", s, e),
+            (Some(s), None) => format!("bytes={}-", s),
+            (None, Some(e)) => format!("bytes=-{}", e),
+            (None, None) => "bytes=0-".to_string(),
+        }
+    }
+}
+
+/// 文件下载选项
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// 并发连接数，1 表示单连接下载
+    pub connections: usize,
+
+    /// 每个分段的大小(字节)
+    pub segment_size: u64,
+
+    /// 单个分段失败后的最大重试次数
+    pub max_retries: u32,
+
+    /// 第一次重试前的等待时间，之后每次翻倍，最长 30 秒
+    pub retry_delay: Duration,
+
+    /// 期望的文件大小(通常取自 `file_metas` 返回的 `size`)，用于下载完成后校验
+    pub expected_size: Option<u64>,
+
+    /// 期望的文件 MD5(通常取自 `file_metas` 返回的 `md5`)，开启 `verify` 时用于校验
+    ///
+    /// 仅 `download_file_with_options` 使用；带元数据的下载直接使用元数据中的 MD5。
+    pub expected_md5: Option<String>,
+
+    /// 下载完成后校验文件大小和 MD5
+    ///
+    /// `download_file_with_options` 没有文件元数据，需要同时设置 `expected_size` 或
+    /// `expected_md5`，否则返回 [`crate::Error::ParamError`]。
+    pub verify: bool,
+
+    /// 校验失败时对本地文件的处理方式
+    pub on_corrupt: CorruptFileAction,
+
+    /// 下载完成后按指定来源设置本地文件的修改/访问时间，None 表示保留下载时间
+    ///
+    /// 需要文件元数据，只能用于 `download_resumable`、`download_fsid` 等方法；
+    /// `download_file_with_options` 设置此项时返回 [`crate::Error::ParamError`]。
+    pub preserve_times: Option<MtimeSource>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            connections: 4,
+            segment_size: 16 * 1024 * 1024,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            expected_size: None,
+            expected_md5: None,
+            verify: false,
+            on_corrupt: CorruptFileAction::default(),
+            preserve_times: None,
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// 创建默认的下载选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置并发连接数
+    pub fn with_connections(mut self, connections: usize) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// 设置分段大小
+    pub fn with_segment_size(mut self, segment_size: u64) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    /// 设置分段最大重试次数
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 设置第一次重试前的等待时间
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// 重试第 `attempt` 次(从 1 开始)前的等待时间
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(MAX_RETRY_DELAY)
+    }
+
+    /// 设置期望的文件大小
+    pub fn with_expected_size(mut self, expected_size: u64) -> Self {
+        self.expected_size = Some(expected_size);
+        self
+    }
+
+    /// 设置期望的文件 MD5
+    pub fn with_expected_md5(mut self, expected_md5: impl Into<String>) -> Self {
+        self.expected_md5 = Some(expected_md5.into());
+        self
+    }
+
+    /// 设置是否在下载完成后校验文件
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// 设置校验失败时对本地文件的处理方式
+    pub fn with_on_corrupt(mut self, on_corrupt: CorruptFileAction) -> Self {
+        self.on_corrupt = on_corrupt;
+        self
+    }
+
+    /// 设置下载完成后本地文件时间的来源
+    pub fn with_preserve_times(mut self, source: MtimeSource) -> Self {
+        self.preserve_times = Some(source);
+        self
+    }
+}
+
+/// 设置本地文件时间时使用的时间来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MtimeSource {
+    /// 服务器修改时间 `mtime`
+    #[default]
+    Server,
+
+    /// 上传时记录的客户端修改时间 `local_mtime`，缺失时使用服务器修改时间
+    LocalMtime,
+}
+
+impl MtimeSource {
+    /// 从服务器返回的时间中选出要设置的时间戳(Unix 秒)
+    pub fn pick(self, mtime: Option<i64>, local_mtime: Option<i64>) -> Option<i64> {
+        match self {
+            MtimeSource::Server => mtime,
+            MtimeSource::LocalMtime => local_mtime.filter(|&t| t > 0).or(mtime),
+        }
+    }
+}
+
+/// 校验失败时对本地文件的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CorruptFileAction {
+    /// 删除文件
+    #[default]
+    Delete,
+
+    /// 重命名为 `<文件名>.corrupt` 隔离保存
+    Quarantine,
+
+    /// 保留文件，仍然放到目标路径
+    Keep,
+}
+
+/// 服务器 MD5 的校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Md5Check {
+    /// 与服务器 MD5 一致
+    Matched,
+
+    /// 服务器返回的不是文件内容的 MD5(如长度不是 32 或包含非十六进制字符的加密值)，无法比较
+    NotContentHash(String),
+
+    /// 服务器未返回 MD5
+    Unavailable,
+}
+
+impl Md5Check {
+    /// 判断服务器返回的 MD5 是否为文件内容的哈希(32 位十六进制)
+    pub fn is_content_hash(md5: &str) -> bool {
+        md5.len() == 32 && md5.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+}
+
+/// 下载文件的校验结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    /// 本地文件大小
+    pub size: u64,
+
+    /// 本地文件的 MD5
+    pub md5: String,
+
+    /// 与服务器 MD5 的比较结果
+    pub md5_check: Md5Check,
+}
+
+/// 断点续传状态，保存在下载临时文件旁的 `.state` 文件中
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadState {
+    /// 文件 ID
+    pub fs_id: i64,
+
+    /// 文件大小
+    pub size: u64,
+
+    /// 服务器返回的 MD5
+    #[serde(default)]
+    pub md5: Option<String>,
+
+    /// 服务器修改时间
+    #[serde(default)]
+    pub mtime: Option<i64>,
+
+    /// 已完成的字节范围 `[start, end]`，按起始位置排序且互不重叠
+    #[serde(default)]
+    pub completed: Vec<(u64, u64)>,
+}
+
+impl DownloadState {
+    /// 判断状态是否对应同一个远程文件(ID、大小、MD5、修改时间均一致)
+    pub fn matches(&self, other: &DownloadState) -> bool {
+        self.fs_id == other.fs_id
+            && self.size == other.size
+            && self.md5 == other.md5
+            && self.mtime == other.mtime
+    }
+
+    /// 记录已完成的范围，并与相邻范围合并
+    pub fn mark_completed(&mut self, start: u64, end: u64) {
+        self.completed.push((start, end));
+        self.completed.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.completed.len());
+        for &(s, e) in &self.completed {
+            match merged.last_mut() {
+                Some(last) if s <= last.1 + 1 => last.1 = last.1.max(e),
+                _ => merged.push((s, e)),
+            }
+        }
+        self.completed = merged;
+    }
+
+    /// 尚未完成的字节范围
+    pub fn missing(&self) -> Vec<(u64, u64)> {
+        let mut missing = Vec::new();
+        let mut next = 0;
+        for &(s, e) in &self.completed {
+            if s > next {
+                missing.push((next, s - 1));
+            }
+            next = next.max(e + 1);
+        }
+        if next < self.size {
+            missing.push((next, self.size - 1));
+        }
+        missing
+    }
+
+    /// 已下载的字节数
+    pub fn downloaded(&self) -> u64 {
+        self.completed.iter().map(|(s, e)| e - s + 1).sum()
+    }
+
+    /// 是否已全部下载
+    pub fn is_complete(&self) -> bool {
+        self.missing().is_empty()
+    }
+}
+
+/// 目录下载选项
+#[derive(Debug, Clone)]
+pub struct DownloadDirOptions {
+    /// 同时下载的文件数量
+    pub concurrency: usize,
+
+    /// 本地文件大小一致且修改时间不早于服务器时跳过下载
+    pub skip_existing: bool,
+
+    /// 单个文件的下载选项
+    pub download: DownloadOptions,
+}
+
+impl Default for DownloadDirOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            skip_existing: true,
+            download: DownloadOptions::default(),
+        }
+    }
+}
+
+impl DownloadDirOptions {
+    /// 创建默认的目录下载选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置并发下载的文件数量
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// 设置是否跳过本地已存在且一致的文件
+    pub fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// 设置单个文件的下载选项
+    pub fn with_download_options(mut self, download: DownloadOptions) -> Self {
+        self.download = download;
+        self
+    }
+}
+
+/// 已下载的文件
+#[derive(Debug, Clone)]
+pub struct DownloadedFile {
+    /// 远程文件路径
+    pub remote_path: String,
+
+    /// 本地文件路径
+    pub local_path: PathBuf,
+
+    /// 文件大小
+    pub size: u64,
+}
+
+/// 因本地文件系统限制而改名的文件或目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamedEntry {
+    /// 远程路径
+    pub remote_path: String,
+
+    /// 实际保存的本地路径
+    pub local_path: PathBuf,
+
+    /// 改名后与同目录其他文件重名，在扩展名前追加了 `~1` 等序号
+    pub deduplicated: bool,
+}
+
+/// 下载失败的文件
+#[derive(Debug)]
+pub struct DownloadFailure {
+    /// 远程文件路径
+    pub remote_path: String,
+
+    /// 本地文件路径
+    pub local_path: PathBuf,
+
+    /// 失败原因
+    pub error: Error,
+}
+
+/// 目录下载结果汇总
+#[derive(Debug, Default)]
+pub struct DownloadDirSummary {
+    /// 下载成功的文件
+    pub downloaded: Vec<DownloadedFile>,
+
+    /// 本地已存在而跳过的文件
+    pub skipped: Vec<PathBuf>,
+
+    /// 下载失败的文件
+    pub failed: Vec<DownloadFailure>,
+
+    /// 文件名不合法而改名保存的文件和目录
+    ///
+    /// 只包含自身名称被改写的条目，位于改名目录下但自身名称未变的文件不会列出。
+    pub renamed: Vec<RenamedEntry>,
+}
+
+impl DownloadDirSummary {
+    /// 是否全部成功
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// 远程文件随机读取选项
+#[derive(Debug, Clone)]
+pub struct RemoteFileOptions {
+    /// 缓存块大小(字节)，每次 Range 请求按块对齐
+    pub block_size: u64,
+
+    /// 读取窗口，一次 Range 请求最多连续读取的块数(至少为 1)
+    ///
+    /// 只在读到未缓存的块时同步请求，不会在后台提前读取；顺序读取时较大的窗口可以减少请求次数。
+    pub fetch_blocks: usize,
+
+    /// LRU 缓存的最大块数
+    pub cache_blocks: usize,
+}
+
+impl Default for RemoteFileOptions {
+    fn default() -> Self {
+        Self {
+            block_size: 256 * 1024,
+            fetch_blocks: 4,
+            cache_blocks: 64,
+        }
+    }
+}
+
+impl RemoteFileOptions {
+    /// 创建默认的随机读取选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置缓存块大小
+    pub fn with_block_size(mut self, block_size: u64) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// 设置一次请求最多读取的块数
+    pub fn with_fetch_blocks(mut self, fetch_blocks: usize) -> Self {
+        self.fetch_blocks = fetch_blocks;
+        self
+    }
+
+    /// 设置 LRU 缓存的最大块数
+    pub fn with_cache_blocks(mut self, cache_blocks: usize) -> Self {
+        self.cache_blocks = cache_blocks;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(size: u64) -> DownloadState {
+        DownloadState {
+            fs_id: 1,
+            size,
+            md5: None,
+            mtime: None,
+            completed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn mark_completed_merges_adjacent_ranges() {
+        let mut state = state(30);
+        state.mark_completed(0, 9);
+        state.mark_completed(10, 19);
+        assert_eq!(state.completed, vec![(0, 19)]);
+        assert_eq!(state.missing(), vec![(20, 29)]);
+        assert_eq!(state.downloaded(), 20);
+    }
+
+    #[test]
+    fn mark_completed_merges_overlapping_ranges() {
+        let mut state = state(30);
+        state.mark_completed(0, 14);
+        state.mark_completed(10, 19);
+        state.mark_completed(5, 8);
+        assert_eq!(state.completed, vec![(0, 19)]);
+        assert_eq!(state.downloaded(), 20);
+    }
+
+    #[test]
+    fn mark_completed_sorts_out_of_order_ranges() {
+        let mut state = state(30);
+        state.mark_completed(20, 29);
+        state.mark_completed(0, 4);
+        assert_eq!(state.completed, vec![(0, 4), (20, 29)]);
+        assert_eq!(state.missing(), vec![(5, 19)]);
+        assert!(!state.is_complete());
+
+        state.mark_completed(5, 19);
+        assert_eq!(state.completed, vec![(0, 29)]);
+        assert!(state.is_complete());
+    }
+
+    #[test]
+    fn missing_covers_gaps_at_both_ends() {
+        let mut state = state(100);
+        assert_eq!(state.missing(), vec![(0, 99)]);
+
+        state.mark_completed(10, 19);
+        state.mark_completed(40, 49);
+        assert_eq!(state.missing(), vec![(0, 9), (20, 39), (50, 99)]);
+    }
+
+    #[test]
+    fn empty_file_is_complete() {
+        assert!(state(0).missing().is_empty());
+    }
+}
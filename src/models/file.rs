@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
 
 /// 文件信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +40,14 @@ pub struct FileInfo {
     #[serde(default)]
     pub md5: Option<String>,
 
+    /// 文件在客户端的创建时间
+    #[serde(default)]
+    pub local_ctime: Option<i64>,
+
+    /// 文件在客户端的修改时间
+    #[serde(default)]
+    pub local_mtime: Option<i64>,
+
     /// 文件来源类型
     #[serde(default)]
     pub from_type: Option<i32>,
@@ -51,7 +60,7 @@ pub struct FileListResponse {
     pub list: Vec<FileInfo>,
 
     /// 游标,用于分页
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_opt_string")]
     pub cursor: Option<String>,
 
     /// 是否有更多数据 (0=否, 1=是)
@@ -59,6 +68,72 @@ pub struct FileListResponse {
     pub has_more: Option<i32>,
 }
 
+/// 列表接口每页最多返回的数量
+const MAX_PAGE_SIZE: i32 = 1000;
+
+/// 自动翻页列表选项
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    /// 排序字段(name/time/size)，仅用于 `file_list_stream`
+    pub order: Option<String>,
+
+    /// 是否降序，仅用于 `file_list_stream`
+    pub desc: bool,
+
+    /// 是否递归列出子目录，仅用于 `file_list_all_stream`
+    pub recursive: bool,
+
+    /// 每页请求的数量，有效范围 1-1000(服务器每页最多返回 1000 条)，超出时按边界值请求
+    pub page_size: i32,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            order: None,
+            desc: false,
+            recursive: false,
+            page_size: MAX_PAGE_SIZE,
+        }
+    }
+}
+
+impl ListOptions {
+    /// 创建默认的列表选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置排序字段
+    pub fn with_order(mut self, order: impl Into<String>) -> Self {
+        self.order = Some(order.into());
+        self
+    }
+
+    /// 设置是否降序
+    pub fn with_desc(mut self, desc: bool) -> Self {
+        self.desc = desc;
+        self
+    }
+
+    /// 设置是否递归列出子目录
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// 设置每页请求的数量，限制在 1-1000 之间
+    pub fn with_page_size(mut self, page_size: i32) -> Self {
+        self.page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        self
+    }
+
+    /// 实际请求的每页数量
+    pub(crate) fn page_limit(&self) -> i32 {
+        self.page_size.clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
 /// 文件预创建响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilePrecreateResponse {
@@ -73,24 +148,157 @@ pub struct FilePrecreateResponse {
     #[serde(default)]
     pub block_list: Option<Vec<i32>>,
 
+    /// 请求 ID
+    #[serde(default, deserialize_with = "deserialize_request_id")]
+    pub request_id: Option<String>,
+
     /// 文件信息(秒传成功时返回)
     #[serde(flatten)]
     pub file_info: Option<FileInfo>,
 }
 
+/// 路径冲突时的处理策略(对应接口的 `rtype` 参数)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OnConflict {
+    /// 不重命名，路径已存在时返回错误
+    Fail,
+
+    /// 路径冲突即重命名
+    #[default]
+    Rename,
+
+    /// 路径冲突且 block_list 不同时才重命名
+    RenameIfDifferent,
+
+    /// 覆盖已存在的文件
+    Overwrite,
+}
+
+impl OnConflict {
+    /// 对应的 `rtype` 取值
+    pub fn rtype(&self) -> i32 {
+        match self {
+            OnConflict::Fail => 0,
+            OnConflict::Rename => 1,
+            OnConflict::RenameIfDifferent => 2,
+            OnConflict::Overwrite => 3,
+        }
+    }
+}
+
+/// 文件在客户端的时间戳(Unix 秒)，上传时作为 `local_ctime` / `local_mtime` 发送
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocalTimes {
+    /// 本地创建时间
+    pub ctime: Option<i64>,
+
+    /// 本地修改时间
+    pub mtime: Option<i64>,
+}
+
+impl LocalTimes {
+    /// 从本地文件元数据读取时间戳，文件系统不支持的字段为 None
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        let to_secs = |time: std::io::Result<std::time::SystemTime>| {
+            time.ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+        };
+
+        Self {
+            ctime: to_secs(metadata.created()),
+            mtime: to_secs(metadata.modified()),
+        }
+    }
+}
+
+/// 分片上传(superfile2)响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SliceUploadResponse {
+    /// 服务器计算的分片 MD5
+    pub md5: String,
+
+    /// 分片序号
+    #[serde(default, deserialize_with = "deserialize_opt_i64")]
+    pub partseq: Option<i64>,
+
+    /// 请求 ID
+    #[serde(default, deserialize_with = "deserialize_request_id")]
+    pub request_id: Option<String>,
+}
+
+/// 上传服务器地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadServer {
+    /// 服务器地址，如 `https://xafj-ct11.pcs.baidu.com`
+    pub server: String,
+}
+
+/// 上传服务器查询(locateupload)响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocateUploadResponse {
+    /// 错误码，0 表示成功
+    #[serde(default)]
+    pub error_code: i32,
+
+    /// 错误信息
+    #[serde(default)]
+    pub error_msg: Option<String>,
+
+    /// 默认上传域名
+    #[serde(default)]
+    pub host: Option<String>,
+
+    /// 推荐的上传服务器
+    #[serde(default)]
+    pub servers: Vec<UploadServer>,
+
+    /// 备用上传服务器
+    #[serde(default)]
+    pub bak_servers: Vec<UploadServer>,
+
+    /// 结果有效期(秒)
+    #[serde(default)]
+    pub expire: Option<i64>,
+
+    /// 请求 ID
+    #[serde(default, deserialize_with = "deserialize_request_id")]
+    pub request_id: Option<String>,
+}
+
+impl LocateUploadResponse {
+    /// 按优先级排列的上传服务器地址(去重，HTTPS 优先，备用服务器在后)
+    pub fn upload_hosts(&self) -> Vec<String> {
+        let mut hosts: Vec<String> = Vec::new();
+        let candidates = self.servers.iter().chain(self.bak_servers.iter());
+        let (https, others): (Vec<_>, Vec<_>) =
+            candidates.partition(|s| s.server.starts_with("https://"));
+
+        for server in https.into_iter().chain(others) {
+            let host = server.server.trim_end_matches('/').to_string();
+            if !host.is_empty() && !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        hosts
+    }
+}
+
 /// 文件创建响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileCreateResponse {
     /// 文件 ID
     pub fs_id: i64,
 
-    /// 文件路径
+    /// 文件路径(服务器最终使用的路径，重命名后与请求路径不同)
     pub path: String,
 
-    /// 服务器文件名
+    /// 服务器文件名(创建目录时不返回)
+    #[serde(default)]
     pub server_filename: String,
 
-    /// 文件大小
+    /// 文件大小(创建目录时不返回)
+    #[serde(default)]
     pub size: i64,
 
     /// MD5 值
@@ -108,6 +316,25 @@ pub struct FileCreateResponse {
 
     /// 文件分类
     pub category: i32,
+
+    /// 请求 ID
+    #[serde(default, deserialize_with = "deserialize_request_id")]
+    pub request_id: Option<String>,
+}
+
+impl FileCreateResponse {
+    /// 如果服务器因路径冲突重命名了文件，返回最终路径
+    ///
+    /// # 参数
+    ///
+    /// * `requested_path` - 请求时使用的路径
+    pub fn renamed_path(&self, requested_path: &str) -> Option<&str> {
+        if self.path.is_empty() || self.path == requested_path {
+            None
+        } else {
+            Some(&self.path)
+        }
+    }
 }
 
 /// 文件操作响应
@@ -120,6 +347,10 @@ pub struct FileOperationResponse {
     /// 操作信息
     #[serde(default)]
     pub info: Option<Vec<FileOperationInfo>>,
+
+    /// 请求 ID
+    #[serde(default, deserialize_with = "deserialize_request_id")]
+    pub request_id: Option<String>,
 }
 
 /// 文件操作信息
@@ -132,6 +363,432 @@ pub struct FileOperationInfo {
     /// 文件 ID
     #[serde(default)]
     pub fs_id: Option<i64>,
+
+    /// 单个文件的错误码，0 表示成功
+    #[serde(default)]
+    pub errno: Option<i32>,
+
+    /// 移动/复制的源路径
+    #[serde(default)]
+    pub from: Option<String>,
+
+    /// 移动/复制后的实际路径(重命名为副本时与请求的目标路径不同)
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+/// 文件管理操作的执行方式，对应请求参数 `async`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OperationMode {
+    /// 同步执行，请求返回时操作已完成(async=0)
+    #[default]
+    Sync,
+
+    /// 由服务器根据文件数量决定同步或异步执行(async=1)
+    Adaptive,
+
+    /// 异步执行，立即返回任务 ID(async=2)，通过 `taskquery` 查询结果
+    Async,
+}
+
+impl OperationMode {
+    /// 对应的 `async` 参数值
+    pub fn async_value(self) -> i32 {
+        match self {
+            OperationMode::Sync => 0,
+            OperationMode::Adaptive => 1,
+            OperationMode::Async => 2,
+        }
+    }
+}
+
+/// 移动或复制时目标路径已存在的处理策略，对应请求参数 `ondup`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnDuplicate {
+    /// 返回错误
+    #[default]
+    Fail,
+
+    /// 重命名为新的副本
+    NewCopy,
+
+    /// 覆盖已存在的文件
+    Overwrite,
+
+    /// 跳过该文件
+    Skip,
+}
+
+impl OnDuplicate {
+    /// 对应的 `ondup` 取值
+    pub fn ondup(&self) -> &'static str {
+        match self {
+            OnDuplicate::Fail => "fail",
+            OnDuplicate::NewCopy => "newcopy",
+            OnDuplicate::Overwrite => "overwrite",
+            OnDuplicate::Skip => "skip",
+        }
+    }
+}
+
+/// 移动或复制的文件项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMoveItem {
+    /// 源文件路径
+    pub path: String,
+
+    /// 目标目录
+    pub dest: String,
+
+    /// 新文件名，None 表示沿用源文件名
+    pub newname: Option<String>,
+}
+
+impl FileMoveItem {
+    /// 把 `path` 移动或复制到目录 `dest` 下，保留原文件名
+    pub fn new(path: impl Into<String>, dest: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            dest: dest.into(),
+            newname: None,
+        }
+    }
+
+    /// 把 `path` 移动或复制到完整的目标路径，自动拆分为目标目录和新文件名
+    ///
+    /// 例如 `/b/c.txt` 拆分为目录 `/b` 和文件名 `c.txt`，`/c.txt` 拆分为 `/` 和 `c.txt`。
+    pub fn to_path(path: impl Into<String>, dest_path: &str) -> Self {
+        let dest_path = dest_path.trim_end_matches('/');
+        let (dest, newname) = match dest_path.rsplit_once('/') {
+            Some(("", name)) => ("/", name),
+            Some((dir, name)) => (dir, name),
+            None => ("/", dest_path),
+        };
+
+        Self {
+            path: path.into(),
+            dest: dest.to_string(),
+            newname: Some(newname.to_string()),
+        }
+    }
+
+    /// 设置新文件名
+    pub fn with_newname(mut self, newname: impl Into<String>) -> Self {
+        self.newname = Some(newname.into());
+        self
+    }
+
+    /// 实际使用的文件名：指定了新文件名时使用新文件名，否则为源文件名
+    pub fn target_name(&self) -> &str {
+        match &self.newname {
+            Some(newname) => newname,
+            None => self
+                .path
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 异步任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    /// 排队中
+    Pending,
+
+    /// 执行中
+    Running,
+
+    /// 执行成功
+    Success,
+
+    /// 执行失败
+    Failed,
+
+    /// 未知状态
+    #[serde(other)]
+    Unknown,
+}
+
+impl TaskStatus {
+    /// 任务是否已结束(成功或失败)
+    pub fn is_finished(self) -> bool {
+        matches!(self, TaskStatus::Success | TaskStatus::Failed)
+    }
+}
+
+/// 异步任务查询响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskQueryResponse {
+    /// 任务状态
+    pub status: TaskStatus,
+
+    /// 任务整体的错误码
+    #[serde(default)]
+    pub task_errno: Option<i32>,
+
+    /// 任务进度(百分比)
+    #[serde(default)]
+    pub progress: Option<i32>,
+
+    /// 每个文件的执行结果
+    #[serde(default)]
+    pub list: Vec<FileOperationInfo>,
+}
+
+impl TaskQueryResponse {
+    /// 任务是否执行成功
+    pub fn is_success(&self) -> bool {
+        self.status == TaskStatus::Success
+    }
+}
+
+/// 异步任务轮询选项
+#[derive(Debug, Clone)]
+pub struct TaskPollOptions {
+    /// 轮询间隔
+    pub interval: Duration,
+
+    /// 等待超时时间，None 表示一直等待
+    pub timeout: Option<Duration>,
+}
+
+impl Default for TaskPollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            timeout: Some(Duration::from_secs(600)),
+        }
+    }
+}
+
+impl TaskPollOptions {
+    /// 创建默认的轮询选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置轮询间隔
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// 设置等待超时时间
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// 批量文件操作选项
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// 执行方式，默认由服务器决定同步或异步；异步执行时自动等待任务结束
+    pub mode: OperationMode,
+
+    /// 等待异步任务时的轮询选项
+    pub poll: TaskPollOptions,
+
+    /// 每次请求包含的最大文件数，超出时自动拆分为多次请求
+    pub chunk_size: usize,
+
+    /// 同时执行的请求数量
+    ///
+    /// 服务器同一时间只允许执行有限的异步任务，并发过高时部分文件会以
+    /// [`FileErrorKind::TaskConflict`] 失败，可通过 [`BatchOutcome::failed_items`] 重试。
+    pub concurrency: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            mode: OperationMode::Adaptive,
+            poll: TaskPollOptions::default(),
+            chunk_size: 500,
+            concurrency: 2,
+        }
+    }
+}
+
+impl BatchOptions {
+    /// 创建默认的批量操作选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置执行方式
+    pub fn with_mode(mut self, mode: OperationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 设置等待异步任务时的轮询选项
+    pub fn with_poll(mut self, poll: TaskPollOptions) -> Self {
+        self.poll = poll;
+        self
+    }
+
+    /// 设置每次请求包含的最大文件数
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// 设置同时执行的请求数量
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+}
+
+/// 单个文件操作失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileErrorKind {
+    /// 文件名错误或无权访问(-7)
+    InvalidPath,
+
+    /// 目标文件或目录已存在(-8、31061)
+    AlreadyExists,
+
+    /// 文件或目录不存在(-9、31066)
+    NotFound,
+
+    /// 网盘空间已满(-10)
+    QuotaExceeded,
+
+    /// 有其他异步任务正在执行(111)
+    TaskConflict,
+
+    /// 请求过于频繁(31034)
+    RateLimited,
+
+    /// 整个请求失败(如网络错误)，没有单个文件的错误码
+    RequestFailed,
+
+    /// 其他错误码
+    Other(i32),
+}
+
+impl FileErrorKind {
+    /// 把错误码映射为错误类型，0 表示成功，返回 None
+    pub fn from_errno(errno: i32) -> Option<Self> {
+        let kind = match errno {
+            0 => return None,
+            -7 => FileErrorKind::InvalidPath,
+            -8 | 31061 => FileErrorKind::AlreadyExists,
+            -9 | 31066 => FileErrorKind::NotFound,
+            -10 => FileErrorKind::QuotaExceeded,
+            111 => FileErrorKind::TaskConflict,
+            31034 => FileErrorKind::RateLimited,
+            other => FileErrorKind::Other(other),
+        };
+        Some(kind)
+    }
+}
+
+impl std::fmt::Display for FileErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileErrorKind::InvalidPath => write!(f, "文件名错误或无权访问"),
+            FileErrorKind::AlreadyExists => write!(f, "文件或目录已存在"),
+            FileErrorKind::NotFound => write!(f, "文件或目录不存在"),
+            FileErrorKind::QuotaExceeded => write!(f, "网盘空间已满"),
+            FileErrorKind::TaskConflict => write!(f, "有其他异步任务正在执行"),
+            FileErrorKind::RateLimited => write!(f, "请求过于频繁"),
+            FileErrorKind::RequestFailed => write!(f, "请求失败"),
+            FileErrorKind::Other(errno) => write!(f, "错误码 {}", errno),
+        }
+    }
+}
+
+/// 批量操作中单个文件的结果
+#[derive(Debug, Clone)]
+pub struct BatchItemResult<T> {
+    /// 请求中的文件项，可直接用于重试
+    pub item: T,
+
+    /// 源路径
+    pub source: String,
+
+    /// 请求的目标路径(删除操作为 None)
+    pub destination: Option<String>,
+
+    /// 操作后的实际路径，服务器重命名为副本时与 `destination` 不同
+    pub new_path: Option<String>,
+
+    /// 错误码，0 表示成功；整个请求失败且没有错误码时为 -1
+    pub errno: i32,
+
+    /// 失败原因，成功时为 None
+    pub error: Option<FileErrorKind>,
+}
+
+/// 异步任务等待超时、结果未知的文件
+#[derive(Debug, Clone)]
+pub struct PendingItem<T> {
+    /// 请求中的文件项
+    pub item: T,
+
+    /// 源路径
+    pub source: String,
+
+    /// 请求的目标路径(删除操作为 None)
+    pub destination: Option<String>,
+
+    /// 仍在执行的异步任务 ID，可通过 `file_task` 继续查询
+    pub taskid: i64,
+}
+
+/// 批量文件操作结果
+#[derive(Debug, Clone)]
+pub struct BatchOutcome<T> {
+    /// 成功的文件
+    pub succeeded: Vec<BatchItemResult<T>>,
+
+    /// 失败的文件
+    pub failed: Vec<BatchItemResult<T>>,
+
+    /// 异步任务在等待时间内没有结束的文件，操作可能仍在服务器上执行，不应直接重试
+    pub pending: Vec<PendingItem<T>>,
+}
+
+impl<T> Default for BatchOutcome<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchOutcome<T> {
+    /// 是否全部成功(没有失败且没有结果未知的文件)
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.pending.is_empty()
+    }
+
+    /// 仍在执行的异步任务 ID(去重，按出现顺序)
+    pub fn pending_tasks(&self) -> Vec<i64> {
+        let mut taskids = Vec::new();
+        for item in &self.pending {
+            if !taskids.contains(&item.taskid) {
+                taskids.push(item.taskid);
+            }
+        }
+        taskids
+    }
+}
+
+impl<T: Clone> BatchOutcome<T> {
+    /// 失败的文件项，可传给对应的批量方法重试
+    pub fn failed_items(&self) -> Vec<T> {
+        self.failed.iter().map(|r| r.item.clone()).collect()
+    }
 }
 
 /// 多媒体文件元数据信息
@@ -173,6 +830,14 @@ pub struct FileMetaInfo {
     #[serde(default)]
     pub md5: Option<String>,
 
+    /// 文件在客户端的创建时间
+    #[serde(default)]
+    pub local_ctime: Option<i64>,
+
+    /// 文件在客户端的修改时间
+    #[serde(default)]
+    pub local_mtime: Option<i64>,
+
     /// 下载链接
     #[serde(default)]
     pub dlink: Option<String>,
@@ -196,3 +861,85 @@ pub struct FileMetasResponse {
     /// 文件元数据列表
     pub list: Vec<FileMetaInfo>,
 }
+
+/// 兼容数字和数字字符串两种形式的字段
+fn deserialize_opt_i64<'de, D>(deserializer: D) -> std::result::Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(i64),
+        String(String),
+    }
+
+    match Option::<NumberOrString>::deserialize(deserializer)? {
+        Some(NumberOrString::Number(n)) => Ok(Some(n)),
+        Some(NumberOrString::String(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// 兼容数字和字符串两种形式的字段，统一转换为字符串
+fn deserialize_opt_string<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(i64),
+        String(String),
+    }
+
+    Ok(
+        Option::<NumberOrString>::deserialize(deserializer)?.map(|value| match value {
+            NumberOrString::Number(n) => n.to_string(),
+            NumberOrString::String(s) => s,
+        }),
+    )
+}
+
+/// 解析请求 ID
+///
+/// 请求 ID 是 64 位无符号整数，可能超出 i64 范围，统一保存为字符串。
+/// 它只用于排查问题，格式无法识别时返回 None，不会导致整个响应解析失败。
+fn deserialize_request_id<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::String(s) if !s.is_empty() => Some(s),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_is_clamped_to_server_limit() {
+        assert_eq!(ListOptions::new().with_page_size(5000).page_size, 1000);
+        assert_eq!(ListOptions::new().with_page_size(0).page_size, 1);
+
+        let options = ListOptions {
+            page_size: 5000,
+            ..ListOptions::new()
+        };
+        assert_eq!(options.page_limit(), 1000);
+    }
+
+    #[test]
+    fn request_id_never_fails_the_response() {
+        let json = r#"{"md5":"abc","request_id":18446744073709551615}"#;
+        let response: SliceUploadResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.request_id.as_deref(), Some("18446744073709551615"));
+
+        let json = r#"{"md5":"abc","request_id":{"unexpected":true}}"#;
+        let response: SliceUploadResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.request_id, None);
+    }
+}
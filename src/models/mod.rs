@@ -1,8 +1,17 @@
 /// 认证相关模型
 pub mod auth;
 
+/// 下载相关模型
+pub mod download;
+
 /// 文件相关模型
 pub mod file;
 
+/// 远程路径模型
+pub mod path;
+
+/// 上传相关模型
+pub mod upload;
+
 /// 用户相关模型
 pub mod user;
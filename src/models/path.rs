@@ -0,0 +1,337 @@
+use crate::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+/// 单个文件名的最大长度(字符)
+pub const MAX_NAME_LEN: usize = 255;
+
+/// 完整路径的最大长度(字符)
+pub const MAX_PATH_LEN: usize = 1000;
+
+/// 文件名中不允许出现的字符
+const FORBIDDEN_CHARS: [char; 8] = ['\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// 网盘中的绝对路径
+///
+/// 创建时按百度网盘的规则校验并规范化，错误的路径在发送请求前就会被拒绝：
+/// - 必须以 `/` 开头，不允许 `..`
+/// - 连续的 `/`、结尾的 `/` 和 `.` 会被去掉
+/// - 文件名不能包含控制字符和 `\ : * ? " < > |`，长度不超过 [`MAX_NAME_LEN`]
+/// - 完整路径长度不超过 [`MAX_PATH_LEN`]
+/// - 统一转换为 Unicode NFC 形式，避免同一个文件名因编码不同而对应不同的路径
+///
+/// 所有接收远程路径的 API 都通过 [`ToRemotePath`] 同时接受 `&str`、`String` 和 `RemotePath`。
+///
+/// # 示例
+/// ```
+/// use baidupan_sdk_rust::models::path::RemotePath;
+///
+/// let dir = RemotePath::new("/apps//myapp/")?;
+/// assert_eq!(dir.as_str(), "/apps/myapp");
+///
+/// let file = dir.join("docs/报告.pdf")?;
+/// assert_eq!(file.file_name(), Some("报告.pdf"));
+/// assert_eq!(file.extension(), Some("pdf"));
+/// assert_eq!(file.strip_prefix(&dir), Some("docs/报告.pdf"));
+///
+/// assert!(RemotePath::new("apps/myapp").is_err());
+/// assert!(RemotePath::new("/apps/../etc").is_err());
+/// # Ok::<(), baidupan_sdk_rust::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RemotePath(String);
+
+impl RemotePath {
+    /// 校验并规范化路径
+    pub fn new(path: &str) -> Result<Self> {
+        if !path.starts_with('/') {
+            return Err(invalid(path, "路径必须以 / 开头"));
+        }
+        Self::root().join_segments(path, path)
+    }
+
+    /// 根目录 `/`
+    pub fn root() -> Self {
+        RemotePath("/".to_string())
+    }
+
+    /// 路径字符串
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否为根目录
+    pub fn is_root(&self) -> bool {
+        self.0 == "/"
+    }
+
+    /// 拼接相对路径，相对路径可以包含多级目录
+    ///
+    /// `relative` 不能以 `/` 开头，也不能包含 `..`。
+    pub fn join(&self, relative: &str) -> Result<Self> {
+        if relative.starts_with('/') {
+            return Err(invalid(relative, "拼接的路径必须是相对路径"));
+        }
+        self.join_segments(relative, relative)
+    }
+
+    /// 上级目录，根目录返回 None
+    pub fn parent(&self) -> Option<RemotePath> {
+        if self.is_root() {
+            return None;
+        }
+        match self.0.rfind('/') {
+            Some(0) => Some(Self::root()),
+            Some(index) => Some(RemotePath(self.0[..index].to_string())),
+            None => None,
+        }
+    }
+
+    /// 文件名(最后一级)，根目录返回 None
+    pub fn file_name(&self) -> Option<&str> {
+        if self.is_root() {
+            return None;
+        }
+        self.0.rsplit('/').next()
+    }
+
+    /// 扩展名(不含 `.`)，没有扩展名或以 `.` 开头的隐藏文件返回 None
+    pub fn extension(&self) -> Option<&str> {
+        let name = self.file_name()?;
+        match name.rfind('.') {
+            Some(0) | None => None,
+            Some(index) => Some(&name[index + 1..]).filter(|ext| !ext.is_empty()),
+        }
+    }
+
+    /// 是否位于 `base` 目录下(包括与 `base` 相同)
+    pub fn starts_with(&self, base: &RemotePath) -> bool {
+        self.strip_prefix(base).is_some()
+    }
+
+    /// 去掉 `base` 前缀，返回相对路径；与 `base` 相同时返回空字符串，不在 `base` 下时返回 None
+    pub fn strip_prefix(&self, base: &RemotePath) -> Option<&str> {
+        if base.is_root() {
+            return Some(&self.0[1..]);
+        }
+        match self.0.strip_prefix(base.as_str())? {
+            "" => Some(""),
+            rest => rest.strip_prefix('/'),
+        }
+    }
+
+    /// 逐级校验 `segments` 并拼接到当前路径后
+    fn join_segments(&self, segments: &str, original: &str) -> Result<Self> {
+        let mut path = self.0.clone();
+        for segment in segments.split('/') {
+            match segment {
+                "" | "." => continue,
+                ".." => return Err(invalid(original, "不允许使用 ..")),
+                _ => {}
+            }
+
+            let segment: String = segment.nfc().collect();
+            if let Some(c) = segment
+                .chars()
+                .find(|c| c.is_control() || FORBIDDEN_CHARS.contains(c))
+            {
+                return Err(invalid(original, &format!("文件名包含非法字符 {:?}", c)));
+            }
+            if segment.chars().count() > MAX_NAME_LEN {
+                return Err(invalid(
+                    original,
+                    &format!("文件名超过 {} 个字符", MAX_NAME_LEN),
+                ));
+            }
+
+            if !path.ends_with('/') {
+                path.push('/');
+            }
+            path.push_str(&segment);
+        }
+
+        if path.chars().count() > MAX_PATH_LEN {
+            return Err(invalid(
+                original,
+                &format!("路径超过 {} 个字符", MAX_PATH_LEN),
+            ));
+        }
+        Ok(RemotePath(path))
+    }
+}
+
+/// 构造路径错误
+fn invalid(path: &str, reason: &str) -> Error {
+    Error::InvalidPath {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+impl fmt::Display for RemotePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for RemotePath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for RemotePath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<&str> for RemotePath {
+    type Error = Error;
+
+    fn try_from(path: &str) -> Result<Self> {
+        Self::new(path)
+    }
+}
+
+impl TryFrom<String> for RemotePath {
+    type Error = Error;
+
+    fn try_from(path: String) -> Result<Self> {
+        Self::new(&path)
+    }
+}
+
+impl From<RemotePath> for String {
+    fn from(path: RemotePath) -> Self {
+        path.0
+    }
+}
+
+/// 可以转换为 [`RemotePath`] 的类型
+///
+/// 转换时会校验路径，因此是可失败的。API 方法的路径参数都接受实现了该 trait 的类型。
+pub trait ToRemotePath {
+    /// 校验并转换为 [`RemotePath`]
+    fn to_remote_path(&self) -> Result<RemotePath>;
+}
+
+impl ToRemotePath for RemotePath {
+    fn to_remote_path(&self) -> Result<RemotePath> {
+        Ok(self.clone())
+    }
+}
+
+impl ToRemotePath for str {
+    fn to_remote_path(&self) -> Result<RemotePath> {
+        RemotePath::new(self)
+    }
+}
+
+impl ToRemotePath for String {
+    fn to_remote_path(&self) -> Result<RemotePath> {
+        RemotePath::new(self)
+    }
+}
+
+impl<T: ToRemotePath + ?Sized> ToRemotePath for &T {
+    fn to_remote_path(&self) -> Result<RemotePath> {
+        (**self).to_remote_path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(s: &str) -> RemotePath {
+        RemotePath::new(s).unwrap()
+    }
+
+    #[test]
+    fn root_is_handled_specially() {
+        let root = RemotePath::root();
+        assert_eq!(path("/"), root);
+        assert_eq!(path("//"), root);
+        assert_eq!(path("/./"), root);
+        assert!(root.is_root());
+        assert_eq!(root.parent(), None);
+        assert_eq!(root.file_name(), None);
+        assert_eq!(path("/a").parent(), Some(root.clone()));
+        assert_eq!(root.join("a/b").unwrap().as_str(), "/a/b");
+        assert_eq!(path("/a/b").strip_prefix(&root), Some("a/b"));
+        assert_eq!(root.strip_prefix(&root), Some(""));
+    }
+
+    #[test]
+    fn dot_segments_are_removed_and_parent_segments_rejected() {
+        assert_eq!(path("/a/./b/.").as_str(), "/a/b");
+        assert!(RemotePath::new("/a/../b").is_err());
+        assert!(RemotePath::new("/..").is_err());
+        assert!(path("/a").join("../b").is_err());
+        assert!(path("/a").join("b/..").is_err());
+        // 以点开头的普通文件名不受影响
+        assert_eq!(path("/a/..b/.c").as_str(), "/a/..b/.c");
+    }
+
+    #[test]
+    fn trailing_and_duplicate_slashes_are_collapsed() {
+        assert_eq!(path("/a//b///c/").as_str(), "/a/b/c");
+        assert_eq!(path("/a/").join("b//c/").unwrap().as_str(), "/a/b/c");
+        assert!(RemotePath::new("a/b").is_err());
+        assert!(RemotePath::new("").is_err());
+        assert!(path("/a").join("/b").is_err());
+    }
+
+    #[test]
+    fn forbidden_characters_are_rejected() {
+        for c in FORBIDDEN_CHARS {
+            assert!(RemotePath::new(&format!("/a{}b", c)).is_err(), "{:?}", c);
+        }
+        assert!(RemotePath::new("/a\nb").is_err());
+        assert!(RemotePath::new("/a\u{0}b").is_err());
+        assert!(path("/a").join("b|c").is_err());
+        assert_eq!(path("/报告 (1).pdf").as_str(), "/报告 (1).pdf");
+    }
+
+    #[test]
+    fn name_and_path_length_limits_are_inclusive() {
+        let name = "文".repeat(MAX_NAME_LEN);
+        assert!(RemotePath::new(&format!("/{}", name)).is_ok());
+        assert!(RemotePath::new(&format!("/{}文", name)).is_err());
+
+        // "/" + 499 个 "a/" 对 + 1 个字符 = MAX_PATH_LEN 个字符
+        let segments = "a/".repeat((MAX_PATH_LEN - 2) / 2);
+        let at_limit = format!("/{}b", segments);
+        assert_eq!(at_limit.chars().count(), MAX_PATH_LEN);
+        assert!(RemotePath::new(&at_limit).is_ok());
+        assert!(RemotePath::new(&format!("{}c", at_limit)).is_err());
+        assert!(path(&at_limit).parent().unwrap().join("bc").is_err());
+    }
+
+    #[test]
+    fn paths_are_normalized_to_nfc() {
+        let nfd = path("/cafe\u{301}/re\u{301}sume\u{301}.txt");
+        let nfc = path("/caf\u{e9}/r\u{e9}sum\u{e9}.txt");
+        assert_eq!(nfd, nfc);
+        assert_eq!(nfd.as_str(), "/caf\u{e9}/r\u{e9}sum\u{e9}.txt");
+        assert_eq!(
+            path("/caf\u{e9}").join("x\u{301}").unwrap().as_str(),
+            "/caf\u{e9}/x\u{301}"
+        );
+    }
+
+    #[test]
+    fn strip_prefix_matches_whole_segments_only() {
+        let base = path("/a/b");
+        assert_eq!(path("/a/b").strip_prefix(&base), Some(""));
+        assert_eq!(path("/a/b/c").strip_prefix(&base), Some("c"));
+        assert_eq!(path("/a/bc").strip_prefix(&base), None);
+        assert_eq!(path("/a").strip_prefix(&base), None);
+        assert!(!path("/a/bc").starts_with(&base));
+        assert!(path("/a/b/c/d").starts_with(&base));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::Error;
+use crate::models::file::{FileCreateResponse, LocalTimes, OnConflict};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 分片重试的最长等待时间
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// 上传进度事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadEvent {
+    /// 开始计算分片 MD5
+    HashStarted {
+        /// 文件总大小
+        total_bytes: u64,
+    },
+
+    /// 分片 MD5 计算进度
+    Hashing {
+        /// 已计算的字节数
+        bytes_hashed: u64,
+        /// 文件总大小
+        total_bytes: u64,
+    },
+
+    /// 分片 MD5 计算完成
+    HashCompleted {
+        /// 分片数量
+        block_count: usize,
+    },
+
+    /// 预创建完成
+    Precreated {
+        /// 上传 ID
+        uploadid: String,
+        /// 是否秒传成功
+        rapid_upload: bool,
+    },
+
+    /// 开始上传分片
+    SliceStarted {
+        /// 分片序号
+        partseq: i32,
+        /// 分片在文件中的偏移
+        offset: u64,
+        /// 分片大小
+        size: u64,
+    },
+
+    /// 分片内的发送进度
+    SliceProgress {
+        /// 分片序号
+        partseq: i32,
+        /// 当前分片已发送的字节数
+        bytes_sent: u64,
+        /// 当前分片大小
+        slice_size: u64,
+        /// 整个文件已发送的字节数
+        total_sent: u64,
+        /// 文件总大小
+        total_bytes: u64,
+    },
+
+    /// 分片上传失败，即将重试
+    SliceRetry {
+        /// 分片序号
+        partseq: i32,
+        /// 第几次重试(从 1 开始)
+        attempt: u32,
+        /// 失败原因
+        reason: String,
+    },
+
+    /// 分片上传完成
+    SliceCompleted {
+        /// 分片序号
+        partseq: i32,
+        /// 分片大小
+        size: u64,
+    },
+
+    /// 文件创建完成
+    Created {
+        /// 文件 ID
+        fs_id: i64,
+        /// 服务器上的文件路径
+        path: String,
+    },
+}
+
+/// 上传进度回调
+///
+/// 回调在上传任务内同步执行，应尽快返回。需要跨线程传递事件时，
+/// 可以在回调中把事件发送到 `tokio::sync::mpsc` 或 `watch` 通道。
+pub type ProgressCallback = Arc<dyn Fn(&UploadEvent) + Send + Sync>;
+
+/// 文件上传选项
+#[derive(Clone)]
+pub struct UploadOptions {
+    /// 分片大小，None 表示按会员等级自动选择
+    ///
+    /// 普通用户只能使用 4MB 分片，会员可使用不超过等级上限的分片大小。
+    pub chunk_size: Option<usize>,
+
+    /// 单个分片失败后的最大重试次数
+    pub max_retries: u32,
+
+    /// 第一次重试前的等待时间，之后每次翻倍，最长 30 秒
+    pub retry_delay: Duration,
+
+    /// 路径冲突时的处理策略，默认重命名
+    pub on_conflict: OnConflict,
+
+    /// 是否发送本地文件的创建/修改时间，默认开启
+    pub preserve_times: bool,
+
+    /// 显式指定的本地时间戳，优先于从文件元数据读取的值
+    pub local_times: Option<LocalTimes>,
+
+    /// 进度回调
+    pub progress: Option<ProgressCallback>,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: None,
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+            on_conflict: OnConflict::default(),
+            preserve_times: true,
+            local_times: None,
+            progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for UploadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("chunk_size", &self.chunk_size)
+            .field("max_retries", &self.max_retries)
+            .field("retry_delay", &self.retry_delay)
+            .field("on_conflict", &self.on_conflict)
+            .field("preserve_times", &self.preserve_times)
+            .field("local_times", &self.local_times)
+            .field("progress", &self.progress.as_ref().map(|_| "<callback>"))
+            .finish()
+    }
+}
+
+impl UploadOptions {
+    /// 创建默认的上传选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置分片大小
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// 设置分片最大重试次数
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 设置第一次重试前的等待时间
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// 重试第 `attempt` 次(从 1 开始)前的等待时间
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(MAX_RETRY_DELAY)
+    }
+
+    /// 设置路径冲突时的处理策略
+    pub fn with_on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.on_conflict = on_conflict;
+        self
+    }
+
+    /// 设置是否发送本地文件的创建/修改时间
+    pub fn with_preserve_times(mut self, preserve_times: bool) -> Self {
+        self.preserve_times = preserve_times;
+        self
+    }
+
+    /// 显式指定本地时间戳
+    pub fn with_local_times(mut self, local_times: LocalTimes) -> Self {
+        self.local_times = Some(local_times);
+        self
+    }
+
+    /// 设置进度回调
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(&UploadEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// 触发进度事件
+    pub(crate) fn emit(&self, event: UploadEvent) {
+        if let Some(progress) = &self.progress {
+            progress(&event);
+        }
+    }
+}
+
+/// 符号链接处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymlinkPolicy {
+    /// 跳过符号链接
+    #[default]
+    Skip,
+
+    /// 跟随符号链接，上传其指向的文件或目录
+    Follow,
+}
+
+/// 目录上传选项
+#[derive(Debug, Clone)]
+pub struct UploadDirOptions {
+    /// 包含规则(glob，匹配相对路径，如 `**/*.jpg`)，为空表示包含所有文件
+    pub include: Vec<String>,
+
+    /// 排除规则(glob，匹配相对路径)，匹配的目录整体跳过
+    pub exclude: Vec<String>,
+
+    /// 符号链接处理策略
+    pub symlinks: SymlinkPolicy,
+
+    /// 远程文件大小和 MD5 与本地一致时跳过上传
+    ///
+    /// 服务器返回的 MD5 不是内容哈希时，改为比较上传时记录的本地修改时间；
+    /// 两者都无法比较的文件记入 [`UploadDirSummary::unverified`]，不会重新上传。
+    pub skip_existing: bool,
+
+    /// 同时上传的文件数量
+    pub concurrency: usize,
+
+    /// 单个文件的上传选项
+    pub upload: UploadOptions,
+}
+
+impl Default for UploadDirOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            symlinks: SymlinkPolicy::default(),
+            skip_existing: false,
+            concurrency: 4,
+            upload: UploadOptions::default(),
+        }
+    }
+}
+
+impl UploadDirOptions {
+    /// 创建默认的目录上传选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加包含规则
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// 添加排除规则
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// 设置符号链接处理策略
+    pub fn with_symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// 设置是否跳过远程已存在且内容一致的文件
+    pub fn with_skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// 设置并发上传数量
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// 设置单个文件的上传选项
+    pub fn with_upload_options(mut self, upload: UploadOptions) -> Self {
+        self.upload = upload;
+        self
+    }
+}
+
+/// 已上传的文件
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    /// 本地文件路径
+    pub local_path: PathBuf,
+
+    /// 远程文件路径
+    pub remote_path: String,
+
+    /// 文件创建响应
+    pub response: FileCreateResponse,
+}
+
+/// 上传失败的文件
+#[derive(Debug)]
+pub struct UploadFailure {
+    /// 本地文件路径
+    pub local_path: PathBuf,
+
+    /// 远程文件路径
+    pub remote_path: String,
+
+    /// 失败原因
+    pub error: Error,
+}
+
+/// 目录上传结果汇总
+#[derive(Debug, Default)]
+pub struct UploadDirSummary {
+    /// 正常上传的文件
+    pub uploaded: Vec<UploadedFile>,
+
+    /// 秒传成功的文件
+    pub rapid_uploaded: Vec<UploadedFile>,
+
+    /// 远程已存在而跳过的文件
+    pub skipped: Vec<PathBuf>,
+
+    /// 远程已存在同样大小的文件，但服务器 MD5 不是内容哈希，也没有可比较的修改时间，
+    /// 无法确认内容是否一致而未上传的文件
+    pub unverified: Vec<PathBuf>,
+
+    /// 上传失败的文件
+    pub failed: Vec<UploadFailure>,
+}
+
+impl UploadDirSummary {
+    /// 是否全部成功
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
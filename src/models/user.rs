@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// 用户信息响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,70 @@ pub struct UserInfoResponse {
     pub uk: Option<i64>,
 }
 
+impl UserInfoResponse {
+    /// 账号会员等级
+    pub fn vip_tier(&self) -> VipTier {
+        VipTier::from_vip_type(self.vip_type.unwrap_or(0))
+    }
+}
+
+/// 会员等级，决定上传分片大小和单文件大小上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VipTier {
+    /// 普通用户
+    Normal,
+    /// 普通会员
+    Vip,
+    /// 超级会员
+    Svip,
+}
+
+impl VipTier {
+    /// 单个文件允许的最大分片数量
+    pub const MAX_SLICE_COUNT: u64 = 1024;
+
+    /// 根据 `vip_type` 获取会员等级，未知值按普通用户处理
+    pub fn from_vip_type(vip_type: i32) -> Self {
+        match vip_type {
+            1 => VipTier::Vip,
+            2 => VipTier::Svip,
+            _ => VipTier::Normal,
+        }
+    }
+
+    /// 上传分片大小上限(字节)
+    ///
+    /// 普通用户固定为 4MB，普通会员 16MB，超级会员 32MB。
+    pub fn max_slice_size(&self) -> usize {
+        match self {
+            VipTier::Normal => 4 * 1024 * 1024,
+            VipTier::Vip => 16 * 1024 * 1024,
+            VipTier::Svip => 32 * 1024 * 1024,
+        }
+    }
+
+    /// 单文件大小上限(字节)
+    ///
+    /// 普通用户 4GB，普通会员 10GB，超级会员 20GB。
+    pub fn max_file_size(&self) -> u64 {
+        match self {
+            VipTier::Normal => 4 * 1024 * 1024 * 1024,
+            VipTier::Vip => 10 * 1024 * 1024 * 1024,
+            VipTier::Svip => 20 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl fmt::Display for VipTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VipTier::Normal => write!(f, "普通用户"),
+            VipTier::Vip => write!(f, "普通会员"),
+            VipTier::Svip => write!(f, "超级会员"),
+        }
+    }
+}
+
 /// 配额信息响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuotaResponse {